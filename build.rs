@@ -1,10 +1,104 @@
 extern crate pkg_config;
 
+use std::env;
+use std::path::Path;
+use std::process;
+
+/// The instructions to install HIDAPI, shown when it could not be found.
+const INSTALL_INSTRUCTIONS: &str = "\
+HIDAPI could not be found, so the crate can't be linked against it.
+
+Install the library and its development files:
+  - Debian/Ubuntu: apt install libhidapi-dev pkg-config
+  - Fedora:        dnf install hidapi-devel pkgconf-pkg-config
+  - Arch Linux:    pacman -S hidapi pkgconf
+  - FreeBSD:       pkg install hidapi pkgconf
+  - macOS:         brew install hidapi
+  - Windows:       download hidapi.lib from https://github.com/libusb/hidapi/releases and
+                   set HIDAPI_LIB_DIR to its directory
+
+On Linux, the `linux-hidraw` feature removes the need for HIDAPI altogether. To build without
+linking (e.g. to only generate the documentation), set DUPLOSENTIDO_SKIP_HIDAPI=1.";
+
 fn main() {
+    println!("cargo:rerun-if-env-changed=DUPLOSENTIDO_SKIP_HIDAPI");
+
+    // Skip linking altogether, which is enough for builds that never run (e.g. documentation).
+    if env::var_os("DUPLOSENTIDO_SKIP_HIDAPI").is_some() {
+        println!("cargo:warning=DUPLOSENTIDO_SKIP_HIDAPI is set, HIDAPI will not be linked");
+        return;
+    }
+
+    // Check the target (not the host) OS, so cross compilation links the correct backend.
+    let target_os = env::var("CARGO_CFG_TARGET_OS");
+
+    // The `linux-hidraw` feature talks to the devices directly, so there is nothing to link.
+    if target_os.as_deref() == Ok("linux") && env::var_os("CARGO_FEATURE_LINUX_HIDRAW").is_some() {
+        return;
+    }
+
+    match target_os.as_deref() {
+        Ok("windows") => link_windows(),
+        Ok("macos") => link_macos(),
+        _ => link_pkg_config(),
+    }
+}
+
+/// Link against HIDAPI using `pkg-config`, preferring the hidraw backend.
+fn link_pkg_config() {
     let pkg = pkg_config::Config::new();
 
-    if pkg.probe("hidapi-hidraw").is_err() {
-        pkg.probe("hidapi-libusb")
-            .expect("Either hidraw or libusb backends should be present");
-    };
+    if pkg.probe("hidapi-hidraw").is_err() && pkg.probe("hidapi-libusb").is_err() {
+        missing_hidapi();
+    }
+}
+
+/// Fail the build because HIDAPI could not be found, explaining how to install it.
+fn missing_hidapi() -> ! {
+    println!("cargo:warning=HIDAPI (hidapi-hidraw or hidapi-libusb) could not be found");
+    eprintln!("{INSTALL_INSTRUCTIONS}");
+
+    process::exit(1);
+}
+
+/// Link against the prebuilt HIDAPI library on Windows.
+///
+/// There is no `pkg-config` on Windows, so the directory of `hidapi.lib` should be supplied
+/// through the `HIDAPI_LIB_DIR` environment variable (unless it is already in the linker path).
+/// Note that `hidapi.dll` must be available at runtime, either next to the executable or in the
+/// `PATH`.
+fn link_windows() {
+    println!("cargo:rerun-if-env-changed=HIDAPI_LIB_DIR");
+
+    if let Ok(dir) = env::var("HIDAPI_LIB_DIR") {
+        println!("cargo:rustc-link-search=native={dir}");
+    }
+    println!("cargo:rustc-link-lib=dylib=hidapi");
+}
+
+/// Link against HIDAPI on macOS, which uses the IOKit backend.
+///
+/// The Homebrew package ships a `pkg-config` file, so it is tried first. If it is not found, the
+/// library is searched in `HIDAPI_LIB_DIR` or in the default Homebrew prefixes.
+fn link_macos() {
+    println!("cargo:rerun-if-env-changed=HIDAPI_LIB_DIR");
+
+    if pkg_config::Config::new().probe("hidapi").is_ok() {
+        return;
+    }
+
+    match env::var("HIDAPI_LIB_DIR") {
+        Ok(dir) => println!("cargo:rustc-link-search=native={dir}"),
+        Err(_) => {
+            for dir in ["/opt/homebrew/lib", "/usr/local/lib"] {
+                if Path::new(dir).join("libhidapi.dylib").exists() {
+                    println!("cargo:rustc-link-search=native={dir}");
+                }
+            }
+        }
+    }
+    println!("cargo:rustc-link-lib=dylib=hidapi");
+    // Only needed when linking against a static HIDAPI, but harmless otherwise.
+    println!("cargo:rustc-link-lib=framework=IOKit");
+    println!("cargo:rustc-link-lib=framework=CoreFoundation");
 }
@@ -0,0 +1,13 @@
+//! A minimal smoke test, binding to the first controller found and printing its state.
+//!
+//! Run it with `cargo run --example smoke` while a controller is connected.
+
+use duplosentido::DualSense;
+
+fn main() {
+    let ds = DualSense::bind().expect("At least one controller should be connected");
+    ds.update()
+        .expect("The controller state should be readable");
+
+    println!("{:#?}", ds.state());
+}
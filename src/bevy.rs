@@ -0,0 +1,134 @@
+//! The Bevy integration module.
+//!
+//! This module provides a [`DualSensePlugin`] for the [Bevy] game engine, pumping the controller
+//! state into the ECS every frame. The controller itself is stored as a non-send resource, since
+//! it can't be shared between threads, and must be inserted by the application:
+//!
+//! ```rust,no_run
+//! use bevy_app::{App, Update};
+//! use bevy_ecs::prelude::*;
+//! use duplosentido::bevy::{ButtonPressed, DualSensePlugin};
+//! use duplosentido::{DualSense, DualSenseState};
+//!
+//! fn log_presses(mut pressed: EventReader<ButtonPressed>, state: Res<DualSenseState>) {
+//!     for ButtonPressed(button) in pressed.read() {
+//!         println!("{button:?} pressed, the left stick is at {:?}", state.left_stick());
+//!     }
+//! }
+//!
+//! let ds = DualSense::bind().expect("At least one controller should be connected");
+//!
+//! App::new()
+//!     .add_plugins(DualSensePlugin)
+//!     .insert_non_send_resource(ds)
+//!     .add_systems(Update, log_presses)
+//!     .run();
+//! ```
+//!
+//! # System ordering
+//! The controller is updated by the [`DualSenseSystems`] set, which runs in the [`PreUpdate`]
+//! schedule. As such, the input is available to every system in [`Update`] in the same frame it
+//! was read. Systems in [`PreUpdate`] that need the input must be ordered after the set.
+//!
+//! [Bevy]: https://bevyengine.org
+//! [`DualSensePlugin`]: struct@crate::bevy::DualSensePlugin
+//! [`DualSenseSystems`]: struct@crate::bevy::DualSenseSystems
+//! [`PreUpdate`]: bevy_app::PreUpdate
+//! [`Update`]: bevy_app::Update
+
+use crate::mappings::Button;
+use crate::{DualSense, DualSenseState};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+
+/// A plugin that updates the [`DualSense`] controller every frame.
+///
+/// Each frame, every state sent by the controller since the previous frame is read, without
+/// blocking. The latest one is stored in the [`DualSenseState`] resource, and the buttons that
+/// changed fire [`ButtonPressed`] and [`ButtonReleased`] events. Until the first state is read, the
+/// resource holds an uninitialized state (see [`DualSenseState::is_initialized`]).
+///
+/// If there is no [`DualSense`] non-send resource, nothing is updated.
+///
+/// ```rust
+/// # #[cfg(feature = "mock")]
+/// # {
+/// use bevy_app::App;
+/// use bevy_ecs::event::Events;
+/// use duplosentido::bevy::{ButtonPressed, DualSensePlugin};
+/// use duplosentido::{mappings::Button, mock::MockDevice, DualSense, DualSenseState};
+///
+/// let device = MockDevice::new();
+/// let mut app = App::new();
+/// app.add_plugins(DualSensePlugin)
+///     .insert_non_send_resource(DualSense::mock(device.clone()).unwrap());
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[8] = 0x08 | 0b0010_0000; // Cross pressed.
+/// device.push_report(&report);
+/// app.update();
+///
+/// assert!(app.world().resource::<DualSenseState>().cross().is_pressed());
+/// let events = app.world().resource::<Events<ButtonPressed>>();
+/// let pressed: Vec<_> = events.get_cursor().read(events).copied().collect();
+/// assert_eq!(pressed, vec![ButtonPressed(Button::Cross)]);
+/// # }
+/// ```
+///
+/// # Errors
+/// Systems have no way of returning errors, so any error while updating the controller (e.g. a
+/// disconnection) stops the update for the current frame. To handle them, use the [`DualSense`]
+/// non-send resource directly.
+///
+/// [`DualSense`]: struct@crate::DualSense
+/// [`DualSenseState`]: struct@crate::DualSenseState
+/// [`DualSenseState::is_initialized`]: fn@crate::DualSenseState::is_initialized
+/// [`ButtonPressed`]: struct@crate::bevy::ButtonPressed
+/// [`ButtonReleased`]: struct@crate::bevy::ButtonReleased
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DualSensePlugin;
+
+impl Plugin for DualSensePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DualSenseState::uninitialized())
+            .add_event::<ButtonPressed>()
+            .add_event::<ButtonReleased>()
+            .add_systems(PreUpdate, update_controller.in_set(DualSenseSystems));
+    }
+}
+
+/// The system set that updates the controller.
+///
+/// See the [module documentation] for more information.
+///
+/// [module documentation]: mod@crate::bevy#system-ordering
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub struct DualSenseSystems;
+
+/// An event fired when a button is pressed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Event)]
+pub struct ButtonPressed(pub Button);
+
+/// An event fired when a button is released.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Event)]
+pub struct ButtonReleased(pub Button);
+
+/// Read every new state of the controller, firing the events of the buttons that changed.
+fn update_controller(
+    controller: Option<NonSend<DualSense>>,
+    mut state: ResMut<DualSenseState>,
+    mut pressed: EventWriter<ButtonPressed>,
+    mut released: EventWriter<ButtonReleased>,
+) {
+    let Some(controller) = controller else {
+        return;
+    };
+
+    while let Ok(Some(new)) = controller.poll() {
+        pressed.write_batch(new.just_pressed(&state).into_iter().map(ButtonPressed));
+        released.write_batch(new.just_released(&state).into_iter().map(ButtonReleased));
+        *state = new;
+    }
+}
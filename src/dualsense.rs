@@ -2,20 +2,41 @@
 //!
 //! This module provides the core functionality for the _duplosentido_ crate.
 
-use crate::hidapi::{self, DeviceWrapper, ProductID, RawInputReportUSB, VendorID};
+use crate::hidapi::{
+    self, DeviceString, DeviceWrapper, HidDevice, ProductID, RawInputReportBT, RawInputReportUSB,
+    VendorID,
+};
+use crate::info::{Calibration, Connection, DeviceInfo, DeviceStrings, FirmwareInfo, PairingInfo};
 use crate::mappings::group::{
-    ActionButtonGroup, BackTriggerGroup, FrontTriggerGroup, MenuGroup, PluggedGroup, PowerGroup,
-    StickGroup,
+    ActionButtonGroup, BackTriggerGroup, EdgeButtons, FrontTriggerGroup, MenuGroup, PluggedGroup,
+    PowerGroup, StickGroup,
 };
 use crate::mappings::{
-    AccelerationState, AngularVelocityState, Axis, BackTriggerEffect, BackTriggerState,
-    BackTriggerStatus, BackTriggerStop, ButtonState, DPadDirection, FingerData, MicrophoneState,
-    MutedState, PluggedState, PowerState, StickCoordinates, StickState, TemperatureState,
-    TouchPadState, USBState,
+    apply_deadzone, AccelerationState, AngularVelocityState, Axis, BackTriggerEffect,
+    BackTriggerState, BackTriggerStatus, BackTriggerStop, Button, ButtonState, DPadDirection,
+    FingerData, MicrophoneState, MutedState, PluggedState, PowerState, StickCalibration,
+    StickCoordinates, StickState, TemperatureState, TouchPadState, TriggerCalibration, USBState,
+    STICK_CENTER,
 };
-
-use std::cell::Cell;
+use crate::output::{
+    self, Color, OutputReport, Outputs, RumbleStep, Sequence, TriggerEffect, TriggerSide,
+    PLAYER_LEDS,
+};
+use crate::poller::PollHandle;
+use crate::recording::{Recorder, Replayer};
+
+use libc::c_int;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+use std::os::fd::BorrowedFd;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
 use thiserror::Error;
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+use tokio::io::{unix::AsyncFd, Interest};
 
 /// A specialized Result type for DualSense controller interactions.
 ///
@@ -47,6 +68,57 @@ pub enum Error {
     /// This error can happen when trying to update the controller state.
     #[error("Could not update controller state")]
     Update,
+    /// A disconnection error.
+    ///
+    /// This error can happen when the controller is disconnected (e.g. the USB cable is unplugged)
+    /// while updating its state. Unlike [`Error::Update`], it will not go away by updating again,
+    /// so the caller should stop updating and wait for the controller to be reconnected.
+    ///
+    /// [`Error::Update`]: crate::Error::Update
+    #[error("Controller was disconnected")]
+    Disconnected,
+    /// An output error.
+    ///
+    /// This error can happen when trying to change the outputs of the controller (e.g. its rumble
+    /// motors).
+    #[error("Could not write controller outputs")]
+    Write,
+    /// A feature report error.
+    ///
+    /// This error can happen when trying to read information from the controller, such as its
+    /// firmware version.
+    #[error("Could not read feature report")]
+    FeatureReport,
+    /// A device information error.
+    ///
+    /// This error can happen when trying to read the strings describing the controller, such as
+    /// its product name.
+    #[error("Could not read device information")]
+    DeviceInfo,
+    /// An unsupported report error.
+    ///
+    /// This error can happen when the controller sends a report that is not yet supported by the
+    /// crate (e.g. a feature or calibration report). The controller state is left untouched, so
+    /// it is safe to skip the report and update again.
+    #[error("Unsupported report (id: {id:#04X}, length: {len})")]
+    UnsupportedReport {
+        /// The report ID (first byte of the report).
+        id: u8,
+        /// The length of the report, in bytes.
+        len: usize,
+    },
+    /// An invalid trigger effect error.
+    ///
+    /// This error can happen when trying to set a back trigger effect whose parameters do not
+    /// make sense together (e.g. a weapon effect that starts and snaps in the same zone). The
+    /// message describes the invalid parameters.
+    #[error("Invalid trigger effect: {0}")]
+    InvalidTriggerEffect(&'static str),
+    /// An I/O error.
+    ///
+    /// This error can happen when reading or writing a recording of the controller reports.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
     /// An exit error.
     ///
     /// This error can happen when trying to finish using the controller (usually when dropping
@@ -63,13 +135,17 @@ impl From<hidapi::Error> for Error {
             hidapi::Error::Open => Error::Bind,
             hidapi::Error::Mode => Error::Mode,
             hidapi::Error::Read => Error::Update,
+            hidapi::Error::Disconnected => Error::Disconnected,
+            hidapi::Error::Write => Error::Write,
+            hidapi::Error::FeatureReport => Error::FeatureReport,
+            hidapi::Error::DeviceString => Error::DeviceInfo,
             hidapi::Error::Exit => Error::Exit,
         }
     }
 }
 
 /// The mode to use when updating the controller state.
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Debug, PartialEq, Copy, Clone)]
 pub enum Mode {
     /// Blocking mode.
     ///
@@ -88,46 +164,515 @@ pub enum Mode {
     NonBlocking = 1,
 }
 
+/// The preferred connection when a controller is connected through both USB and Bluetooth.
+///
+/// A controller plugged through USB while paired through Bluetooth is seen as two devices, one
+/// for each connection. The preference, set with [`prefer`], decides which one is binded to.
+///
+/// [`prefer`]: fn@crate::DualSense::prefer
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ConnectionPreference {
+    /// No preference, the controllers are binded to in the order the platform lists them.
+    #[default]
+    Any,
+    /// Prefer USB, which has a lower latency, falling back to Bluetooth.
+    Usb,
+    /// Prefer Bluetooth, falling back to USB.
+    Bluetooth,
+}
+
+impl ConnectionPreference {
+    /// Get the rank of `connection`, where the preferred connection has the lowest rank.
+    fn rank(&self, connection: Connection) -> u8 {
+        match (self, connection) {
+            (ConnectionPreference::Any, _) => 0,
+            (ConnectionPreference::Usb, Connection::Usb) => 0,
+            (ConnectionPreference::Usb, Connection::Bluetooth) => 1,
+            (ConnectionPreference::Bluetooth, Connection::Bluetooth) => 0,
+            (ConnectionPreference::Bluetooth, Connection::Usb) => 1,
+        }
+    }
+}
+
+/// A known controller model.
+///
+/// Every model has its own product ID, so each one has to be searched for separately when binding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KnownController {
+    /// The standard DualSense controller.
+    DualSense,
+    /// The DualSense Edge controller.
+    ///
+    /// The Edge sends the same report as the standard controller, so all of its common inputs are
+    /// supported. Its extra inputs (the back paddles and the function buttons) are available
+    /// through [`DualSenseState::edge`].
+    ///
+    /// [`DualSenseState::edge`]: fn@crate::DualSenseState::edge
+    DualSenseEdge,
+}
+
+impl KnownController {
+    /// All of the known controllers, in the order they are searched for by [`bind`].
+    ///
+    /// [`bind`]: fn@crate::DualSense::bind
+    pub const ALL: [KnownController; 2] =
+        [KnownController::DualSense, KnownController::DualSenseEdge];
+
+    /// Get the vendor ID of the controller.
+    pub const fn vendor_id(&self) -> u16 {
+        0x054C
+    }
+
+    /// Get the product ID of the controller.
+    pub const fn product_id(&self) -> u16 {
+        match self {
+            KnownController::DualSense => 0x0CE6,
+            KnownController::DualSenseEdge => 0x0DF2,
+        }
+    }
+
+    /// Get the name of the controller, as reported by the device itself.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            KnownController::DualSense => "DualSense Wireless Controller",
+            KnownController::DualSenseEdge => "DualSense Edge Wireless Controller",
+        }
+    }
+
+    /// Get the [SDL] `GameControllerDB` mapping line of the controller.
+    ///
+    /// The mapping follows the layout of the USB input report, as exposed by a generic HID
+    /// joystick driver, and targets the platform the crate was compiled for.
+    ///
+    /// ```rust
+    /// use duplosentido::KnownController;
+    ///
+    /// let mapping = KnownController::DualSense.sdl_mapping();
+    /// assert!(mapping.starts_with("030000004c050000e60c000000000000,DualSense Wireless Controller,"));
+    /// assert!(mapping.contains(",a:b1,b:b2,"));
+    /// assert!(mapping.ends_with(','));
+    /// ```
+    ///
+    /// [SDL]: https://github.com/mdqinc/SDL_GameControllerDB
+    pub fn sdl_mapping(&self) -> String {
+        // SDL expects the mappings sorted by name.
+        const MAPPINGS: [(&str, &str); 22] = [
+            ("a", "b1"),
+            ("b", "b2"),
+            ("back", "b8"),
+            ("dpdown", "h0.4"),
+            ("dpleft", "h0.8"),
+            ("dpright", "h0.2"),
+            ("dpup", "h0.1"),
+            ("guide", "b12"),
+            ("leftshoulder", "b4"),
+            ("leftstick", "b10"),
+            ("lefttrigger", "a3"),
+            ("leftx", "a0"),
+            ("lefty", "a1"),
+            ("misc1", "b14"),
+            ("rightshoulder", "b5"),
+            ("rightstick", "b11"),
+            ("righttrigger", "a4"),
+            ("rightx", "a2"),
+            ("righty", "a5"),
+            ("start", "b9"),
+            ("touchpad", "b13"),
+            ("x", "b0"),
+        ];
+
+        let platform = if cfg!(target_os = "windows") {
+            "Windows"
+        } else if cfg!(target_os = "macos") {
+            "Mac OS X"
+        } else {
+            "Linux"
+        };
+
+        // The GUID is made of the bus type (USB), the vendor ID, the product ID and the version,
+        // each as a little-endian 16-bit value padded to 32 bits.
+        let [vendor_lo, vendor_hi] = self.vendor_id().to_le_bytes();
+        let [product_lo, product_hi] = self.product_id().to_le_bytes();
+        let mut mapping = format!(
+            "03000000{vendor_lo:02x}{vendor_hi:02x}0000{product_lo:02x}{product_hi:02x}0000\
+             00000000,{},",
+            self.name()
+        );
+        for (name, input) in MAPPINGS {
+            mapping.push_str(&format!("{name}:{input},"));
+        }
+        mapping.push_str(&format!("platform:{platform},"));
+
+        mapping
+    }
+}
+
 /// A bind to a DualSense controller.
+///
+/// # Threads
+/// A controller is [`Send`], so it can be owned by a dedicated input thread, even if it was
+/// binded in another one. It is *not* [`Sync`], though, since HIDAPI does not support using the
+/// same device from multiple threads at once. To share the state between threads, see
+/// [`spawn_poller`].
+///
+/// ```rust,no_run
+/// use duplosentido::DualSense;
+/// use std::thread;
+///
+/// let ds = DualSense::bind().expect("At least one controller should be connected");
+///
+/// let input = thread::spawn(move || loop {
+///     ds.update().unwrap();
+///     if ds.state().home_menu().is_pressed() {
+///         break;
+///     }
+/// });
+///
+/// input.join().unwrap();
+/// ```
+///
+/// [`spawn_poller`]: fn@crate::DualSense::spawn_poller
 pub struct DualSense {
-    controller: hidapi::DeviceWrapper,
+    controller: Box<dyn HidDevice>,
     // Use `RefCell` to avoid the need for the user to declare the controller as `mut`, since it
     // isn't intuitive for it to be `mut` in this case, as `mut` should imply that we are mutating
     // the controller itself.
     state: Cell<DualSenseState>,
+    report: Cell<RawInputReportUSB>,
     mode: Cell<Mode>,
+    kind: KnownController,
+    stick_deadzone: Cell<f32>,
+    stick_calibration: Cell<StickCalibration>,
+    stick_y_invert: Cell<bool>,
+    trigger_calibration: Cell<TriggerCalibration>,
+    has_update: Cell<bool>,
+    // When the last report was read, and the time elapsed between the last two reports.
+    last_report: Cell<Option<Instant>>,
+    report_interval: Cell<Option<Duration>>,
+    rumble_pattern: RefCell<Option<Sequence<(u8, u8)>>>,
+    rumble_scale: Cell<(f32, f32)>,
+    lightbar_fade: RefCell<Option<Sequence<Color>>>,
+    // The error of the last failed write of a sequence, returned by the next update.
+    output_error: RefCell<Option<Error>>,
+    // The last outputs written to the controller, so they can be written again.
+    outputs: Cell<Outputs>,
 }
 
 impl DualSense {
     /// Try connecting with a DualSense controller.
     ///
-    /// In case multiple controllers are found, only the first one listed will be binded to.
+    /// In case multiple controllers are found, only the first one listed will be binded to. Every
+    /// [`KnownController`] is searched for, so a DualSense Edge is also binded to if there is no
+    /// standard DualSense connected.
     ///
     /// This method can fail either if a DualSense controller is not found or if it wasn't possible
     /// to bind with the controller.
+    ///
+    /// The controller starts in _blocking_ mode. To start in another mode, use
+    /// [`bind_with_mode`].
+    ///
+    /// [`bind_with_mode`]: fn@crate::DualSense::bind_with_mode
+    /// [`KnownController`]: enum@crate::KnownController
     pub fn bind() -> Result<Self> {
-        const VENDOR_ID: VendorID = VendorID::new(0x054C);
-        const PRODUCT_ID: ProductID = ProductID::new(0x0CE6);
-
-        match DeviceWrapper::open(VENDOR_ID, PRODUCT_ID) {
-            Ok(controller) => {
-                // Set the mode to blocking. Since the default DualSense poll rate is 250hz, every
-                // 4ms we receive a new reading.
-                controller.set_mode(Mode::Blocking)?;
-
-                // TODO: Find the best approach to handle uninitialized (not `update`d) controller
-                // state.
-                let state = DualSenseState::from(RawInputReportUSB::new([0_u8; 64])).into();
-                let mode = Mode::Blocking.into();
-
-                Ok(DualSense {
-                    controller,
-                    state,
-                    mode,
-                })
-            }
-            Err(e) => Err(e.into()),
-        }
+        DualSense::bind_with_mode(Mode::Blocking)
+    }
+
+    /// Count the connected DualSense controllers, without binding to any of them.
+    ///
+    /// Every [`KnownController`] is counted. Since no controller is opened, this method is cheap
+    /// and has no side effects, which makes it suitable to be called repeatedly (e.g. while
+    /// waiting for the user to plug in a controller):
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::DualSense;
+    /// use std::{thread, time::Duration};
+    ///
+    /// while DualSense::count().unwrap() == 0 {
+    ///     println!("Please connect a controller");
+    ///     thread::sleep(Duration::from_secs(1));
+    /// }
+    ///
+    /// let ds = DualSense::bind().unwrap();
+    /// ```
+    ///
+    /// Note that a controller connected through both USB and Bluetooth at the same time may be
+    /// counted twice, once for each connection.
+    ///
+    /// [`KnownController`]: enum@crate::KnownController
+    pub fn count() -> Result<usize> {
+        let count = KnownController::ALL
+            .iter()
+            .map(|kind| {
+                hidapi::count(
+                    VendorID::new(kind.vendor_id()),
+                    ProductID::new(kind.product_id()),
+                )
+            })
+            .sum();
+
+        Ok(count)
+    }
+
+    /// List the connected DualSense controllers, without binding to any of them.
+    ///
+    /// Every [`KnownController`] is listed, in the order they are searched for by [`bind`]. If a
+    /// [`ConnectionPreference`] was set with [`prefer`], the controllers with the preferred
+    /// connection are listed first. Any of the listed controllers can then be binded to with
+    /// [`bind_path`]:
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::DualSense;
+    ///
+    /// for info in DualSense::list().unwrap() {
+    ///     println!("{:?} at {}", info.kind(), info.path());
+    /// }
+    ///
+    /// let first = DualSense::list().unwrap().remove(0);
+    /// let ds = DualSense::bind_path(first.path()).unwrap();
+    /// ```
+    ///
+    /// [`KnownController`]: enum@crate::KnownController
+    /// [`ConnectionPreference`]: enum@crate::ConnectionPreference
+    /// [`bind`]: fn@crate::DualSense::bind
+    /// [`prefer`]: fn@crate::DualSense::prefer
+    /// [`bind_path`]: fn@crate::DualSense::bind_path
+    pub fn list() -> Result<Vec<DeviceInfo>> {
+        let mut list: Vec<DeviceInfo> = KnownController::ALL
+            .into_iter()
+            .flat_map(|kind| {
+                let vendor_id = VendorID::new(kind.vendor_id());
+                let product_id = ProductID::new(kind.product_id());
+
+                hidapi::enumerate(vendor_id, product_id)
+                    .into_iter()
+                    .map(move |device| DeviceInfo {
+                        path: device.path,
+                        kind,
+                        serial_number: device.serial_number,
+                        connection: device.connection,
+                    })
+            })
+            .collect();
+
+        // The sort is stable, so the controllers are still in the order they are searched for.
+        let preference = DualSense::preference();
+        list.sort_by_key(|info| preference.rank(info.connection));
+
+        Ok(list)
+    }
+
+    /// Set which connection to prefer when binding to a controller connected through both USB and
+    /// Bluetooth.
+    ///
+    /// The preference is global, and is used by every following call to [`bind`] (and its
+    /// variations) and [`list`]. With [`ConnectionPreference::Usb`], the lower latency USB
+    /// connection is binded to when available, falling back to Bluetooth otherwise:
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{ConnectionPreference, DualSense};
+    ///
+    /// DualSense::prefer(ConnectionPreference::Usb);
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    /// ```
+    ///
+    /// The preference only orders the controllers, it never filters them out. A specific
+    /// controller is selected with [`bind_path`], which ignores the preference. There is no way of
+    /// binding by serial number, since a controller connected through both USB and Bluetooth may
+    /// report the same serial number for both connections, so the path must be used instead.
+    ///
+    /// [`bind`]: fn@crate::DualSense::bind
+    /// [`list`]: fn@crate::DualSense::list
+    /// [`bind_path`]: fn@crate::DualSense::bind_path
+    /// [`ConnectionPreference::Usb`]: crate::ConnectionPreference::Usb
+    pub fn prefer(preference: ConnectionPreference) {
+        *PREFERENCE.lock().unwrap_or_else(PoisonError::into_inner) = preference;
+    }
+
+    /// Get the connection preference set with [`prefer`].
+    ///
+    /// [`prefer`]: fn@crate::DualSense::prefer
+    pub fn preference() -> ConnectionPreference {
+        *PREFERENCE.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Try connecting with the DualSense controller at `path`, as listed by [`list`].
+    ///
+    /// This method behaves exactly like [`bind`], but binds to a specific controller. This is
+    /// useful when multiple controllers are connected, especially on Linux, where a controller
+    /// connected through USB may not report a usable serial number.
+    ///
+    /// If there is no known controller at `path`, [`Error::Bind`] is returned.
+    ///
+    /// [`list`]: fn@crate::DualSense::list
+    /// [`bind`]: fn@crate::DualSense::bind
+    /// [`Error::Bind`]: crate::Error::Bind
+    pub fn bind_path(path: &str) -> Result<Self> {
+        DualSense::new(Mode::Blocking, || {
+            let info = DualSense::list()?
+                .into_iter()
+                .find(|info| info.path == path)
+                .ok_or(Error::Bind)?;
+
+            let vendor_id = VendorID::new(info.kind.vendor_id());
+            let product_id = ProductID::new(info.kind.product_id());
+            let controller = DeviceWrapper::open_path(vendor_id, product_id, path)?;
+
+            Ok((info.kind, controller))
+        })
+    }
+
+    /// Try connecting with a DualSense controller, starting in the given mode.
+    ///
+    /// This method behaves exactly like [`bind`], but the mode is applied as part of the bind, so
+    /// the controller never runs in a mode other than `mode`.
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{DualSense, Mode};
+    ///
+    /// let ds = DualSense::bind_with_mode(Mode::NonBlocking)
+    ///     .expect("At least one controller should be connected");
+    ///
+    /// assert_eq!(ds.mode(), Mode::NonBlocking);
+    /// ```
+    ///
+    /// [`bind`]: fn@crate::DualSense::bind
+    pub fn bind_with_mode(mode: Mode) -> Result<Self> {
+        DualSense::new(mode, || DualSense::open(None))
+    }
+
+    /// Try connecting with a specific controller model.
+    ///
+    /// This method behaves exactly like [`bind`], but only the given model is searched for.
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{DualSense, KnownController};
+    ///
+    /// let ds = DualSense::bind_controller(KnownController::DualSenseEdge)
+    ///     .expect("A DualSense Edge should be connected");
+    ///
+    /// assert_eq!(ds.kind(), KnownController::DualSenseEdge);
+    /// ```
+    ///
+    /// [`bind`]: fn@crate::DualSense::bind
+    pub fn bind_controller(kind: KnownController) -> Result<Self> {
+        DualSense::new(Mode::Blocking, || DualSense::open(Some(kind)))
+    }
+
+    /// Try connecting with a compatible controller with the given `vendor` and `product` IDs.
+    ///
+    /// Some third-party controllers (e.g. from Nacon or Victrix) speak the same report format as
+    /// the DualSense, but with their own IDs. This method behaves exactly like [`bind`], but
+    /// searches for the given IDs instead of the ones of the [`KnownController`] models, which
+    /// are the default. The controller is treated as a standard DualSense, so [`kind`] returns
+    /// [`KnownController::DualSense`].
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::DualSense;
+    ///
+    /// let ds = DualSense::bind_vid_pid(0x146B, 0x0D0A)
+    ///     .expect("A compatible controller should be connected");
+    /// ```
+    ///
+    /// # Compatibility
+    /// There is no guarantee that the reports of an unknown device are compatible. Many clones
+    /// work, but others may send reports that are not supported (see [`Error::UnsupportedReport`])
+    /// or, worse, reports with the same ID and length but a different layout, which are parsed
+    /// into a wrong state.
+    ///
+    /// [`bind`]: fn@crate::DualSense::bind
+    /// [`kind`]: fn@crate::DualSense::kind
+    /// [`KnownController`]: enum@crate::KnownController
+    /// [`KnownController::DualSense`]: crate::KnownController::DualSense
+    /// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+    pub fn bind_vid_pid(vendor: u16, product: u16) -> Result<Self> {
+        DualSense::new(Mode::Blocking, || {
+            let controller = DeviceWrapper::open(VendorID::new(vendor), ProductID::new(product))?;
+
+            Ok((KnownController::DualSense, controller))
+        })
+    }
+
+    /// Open the first controller listed by [`list`], optionally only of the given model.
+    ///
+    /// [`list`]: fn@crate::DualSense::list
+    fn open(kind: Option<KnownController>) -> Result<(KnownController, DeviceWrapper)> {
+        DualSense::list()?
+            .into_iter()
+            .filter(|info| kind.is_none_or(|kind| info.kind == kind))
+            .find_map(|info| {
+                let vendor_id = VendorID::new(info.kind.vendor_id());
+                let product_id = ProductID::new(info.kind.product_id());
+
+                DeviceWrapper::open_path(vendor_id, product_id, &info.path)
+                    .ok()
+                    .map(|controller| (info.kind, controller))
+            })
+            .ok_or(Error::Bind)
+    }
+
+    /// Bind to a mock device instead of a real controller.
+    ///
+    /// The returned controller reads its reports from `device`, so it behaves exactly like a real
+    /// one, without the need for any hardware. See [`MockDevice`] for more information.
+    ///
+    /// [`MockDevice`]: struct@crate::mock::MockDevice
+    #[cfg(feature = "mock")]
+    pub fn mock(device: crate::mock::MockDevice) -> Result<Self> {
+        DualSense::new(Mode::Blocking, || Ok((KnownController::DualSense, device)))
+    }
+
+    /// Bind to a recording instead of a real controller.
+    ///
+    /// The returned controller reads its reports from `replayer`, so code that uses a controller
+    /// can be driven by a previous recording. See [`Replayer`] for more information.
+    ///
+    /// [`Replayer`]: struct@crate::recording::Replayer
+    pub fn replay(replayer: Replayer) -> Result<Self> {
+        DualSense::new(Mode::Blocking, || {
+            Ok((KnownController::DualSense, replayer))
+        })
+    }
+
+    /// Create a bind from the device opened by `open`, in the given mode.
+    ///
+    /// The bind count is locked while the device is opened, so HIDAPI can't be finalized by
+    /// another bind being dropped in the meantime (see [`BINDS`]).
+    fn new<D: HidDevice + 'static>(
+        mode: Mode,
+        open: impl FnOnce() -> Result<(KnownController, D)>,
+    ) -> Result<Self> {
+        let mut binds = BINDS.lock().unwrap_or_else(PoisonError::into_inner);
+        let (kind, controller) = open()?;
+
+        // Since the default DualSense poll rate is 250hz, in blocking mode we receive a new
+        // reading every 4ms.
+        controller.set_mode(mode)?;
+
+        let report = RawInputReportUSB::new([0_u8; 64]).into();
+        let state = DualSenseState::uninitialized().into();
+        let mode = mode.into();
+
+        *binds += 1;
+
+        Ok(DualSense {
+            controller: Box::new(controller),
+            state,
+            report,
+            mode,
+            kind,
+            stick_deadzone: 0.0.into(),
+            stick_calibration: StickCalibration::default().into(),
+            stick_y_invert: false.into(),
+            trigger_calibration: TriggerCalibration::default().into(),
+            has_update: false.into(),
+            last_report: None.into(),
+            report_interval: None.into(),
+            rumble_pattern: None.into(),
+            rumble_scale: (1.0, 1.0).into(),
+            lightbar_fade: None.into(),
+            output_error: None.into(),
+            outputs: Outputs::default().into(),
+        })
     }
 
     /// Update the current controller state.
@@ -139,6 +684,29 @@ impl DualSense {
     /// operations to update the controller state. After that, you can call [`state`] and use the
     /// state of the controller without the need of updating for every state check.
     ///
+    /// Both the USB report (`0x01`, 64 bytes) and the full Bluetooth report (`0x31`, 78 bytes) are
+    /// supported. They carry the same inputs, so the state does not depend on the connection:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// // The inputs start one byte later, after the sequence number of the report.
+    /// let mut report = [0_u8; 78];
+    /// report[0] = 0x31;
+    /// report[9] = 0x08 | 0b0010_0000; // Cross pressed.
+    /// device.push_report(&report);
+    ///
+    /// assert_eq!(ds.update().unwrap(), 78);
+    /// assert!(ds.state().cross().is_pressed());
+    /// assert_eq!(ds.raw_report()[8], 0x08 | 0b0010_0000);
+    /// # }
+    /// ```
+    ///
     /// # Blocking mode
     /// In _blocking_ mode, if the method returns `Ok`, it is *guaranteed* to have updated the
     /// state. As such, you can safely ignore the number of returned bytes.
@@ -147,65 +715,1761 @@ impl DualSense {
     /// In _non-blocking_ mode there is no guarantee that the call to [`update`] read any bytes.
     /// For that reason, you should check the number of bytes read by the method.
     ///
+    /// # Errors
+    /// If the controller sends a report that is not supported, or a supported report with fields
+    /// that can't be parsed (e.g. a directional pad direction above `8`),
+    /// [`Error::UnsupportedReport`] is returned and the state is not updated. This error is
+    /// recoverable, so the caller can simply skip it and call [`update`] again:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense, Error};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x09;
+    /// device.push_report(&report);
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    ///
+    /// assert!(matches!(ds.update(), Err(Error::UnsupportedReport { .. })));
+    /// assert_eq!(ds.update().unwrap(), 64);
+    /// # }
+    /// ```
+    ///
+    /// If the controller was disconnected, [`Error::Disconnected`] is returned. Every following
+    /// call will also fail, so the caller should stop updating the controller.
+    ///
     /// [`update`]: fn@crate::DualSense::update
     /// [`state`]: fn@crate::DualSense::state
+    /// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+    /// [`Error::Disconnected`]: crate::Error::Disconnected
     pub fn update(&self) -> Result<usize> {
-        // 64 bytes is the maximum size of a packet in wired mode, so we can use a known size
-        // slice. For Bluetooth mode, it seems that reports can get as big as 546 bytes (!), so if
-        // we plan on supporting it in the future, we may need to change the slice to a `Vec`.
-        let mut buffer = [0_u8; 64];
+        // The buffer fits the largest supported report, so any longer report is truncated and
+        // then rejected for its length.
+        let mut buffer = [0_u8; MAX_INPUT_REPORT_LEN];
+        self.take_output_error()?;
         let bytes = self.controller.read(&mut buffer)?;
+
+        self.store(&buffer, bytes)
+    }
+
+    /// Update the current controller state, reading the report into `buf`.
+    ///
+    /// This method behaves exactly like [`update`], but the report is also copied into the
+    /// caller's buffer, so the raw bytes are available alongside the parsed state without calling
+    /// [`raw_report`]. Like [`raw_report`], the inputs are always laid out as in the USB report,
+    /// even when the controller is connected through Bluetooth. If no report was read (or it was
+    /// not supported), `buf` is left untouched.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08 | 0b0010_0000; // Cross pressed.
+    /// device.push_report(&report);
+    ///
+    /// let mut buf = [0_u8; 64];
+    /// assert_eq!(ds.update_into(&mut buf).unwrap(), 64);
+    /// assert_eq!(buf, report);
+    /// assert!(ds.state().cross().is_pressed());
+    /// # }
+    /// ```
+    ///
+    /// [`update`]: fn@crate::DualSense::update
+    /// [`raw_report`]: fn@crate::DualSense::raw_report
+    pub fn update_into(&self, buf: &mut [u8; 64]) -> Result<usize> {
+        let bytes = self.update()?;
+        if bytes > 0 {
+            *buf = self.raw_report();
+        }
+
+        Ok(bytes)
+    }
+
+    /// Update the current controller state, waiting at most `timeout` for new data.
+    ///
+    /// This method is a middle ground between the _blocking_ and _non-blocking_ modes, and it
+    /// works independently of the current [`mode`]. A `timeout` of zero behaves like a
+    /// _non-blocking_ [`update`]. Timeouts smaller than a millisecond are rounded up to one
+    /// millisecond.
+    ///
+    /// Like [`update`], this method returns the number of bytes read. If it returns `0`, the
+    /// timeout elapsed without any new data and the state was not updated.
+    ///
+    /// [`mode`]: fn@crate::DualSense::mode
+    /// [`update`]: fn@crate::DualSense::update
+    pub fn update_timeout(&self, timeout: Duration) -> Result<usize> {
+        let milliseconds = match timeout.as_millis() {
+            0 if !timeout.is_zero() => 1,
+            ms => ms.min(c_int::MAX as u128) as c_int,
+        };
+
+        let mut buffer = [0_u8; MAX_INPUT_REPORT_LEN];
+        self.take_output_error()?;
+        let bytes = self.controller.read_timeout(&mut buffer, milliseconds)?;
+
+        self.store(&buffer, bytes)
+    }
+
+    /// Check for a new controller state, without blocking.
+    ///
+    /// This method reads from the controller without blocking, independently of the current
+    /// [`mode`] (which is left untouched). If a new state was read, it is returned, otherwise
+    /// `None` is returned and the state is not updated. This makes it a single call for game loops:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense, Mode};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// assert!(ds.poll().unwrap().is_none());
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08 | 0b0010_0000; // Cross pressed.
+    /// device.push_report(&report);
+    ///
+    /// let state = ds.poll().unwrap().expect("A report was pushed");
+    /// assert!(state.cross().is_pressed());
+    /// assert_eq!(ds.mode(), Mode::Blocking);
+    /// # }
+    /// ```
+    ///
+    /// [`mode`]: fn@crate::DualSense::mode
+    pub fn poll(&self) -> Result<Option<DualSenseState>> {
+        match self.update_timeout(Duration::ZERO)? {
+            0 => Ok(None),
+            _ => Ok(Some(self.state())),
+        }
+    }
+
+    /// Read every report buffered by the controller, keeping only the latest state.
+    ///
+    /// When the reports are not read fast enough (e.g. after a hitch in a game loop), they pile up
+    /// and the following updates return stale input. This method reads without blocking until no
+    /// data remains, independently of the current [`mode`] (which is left untouched), so the state
+    /// is resynced to the freshest input. It returns the number of reports discarded, which does
+    /// not include the one kept as the current state:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// device.push_report(&report);
+    /// report[8] |= 0b0010_0000; // Cross pressed.
+    /// device.push_report(&report);
+    ///
+    /// assert_eq!(ds.drain().unwrap(), 2);
+    /// assert!(ds.state().cross().is_pressed());
+    /// assert_eq!(device.pending(), 0);
+    ///
+    /// // Unsupported reports are never kept, so they are all discarded.
+    /// device.push_report(&[0x02; 64]);
+    /// assert_eq!(ds.drain().unwrap(), 1);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Unsupported reports are skipped (and counted as discarded), while any other error stops
+    /// the drain and is returned.
+    ///
+    /// [`mode`]: fn@crate::DualSense::mode
+    pub fn drain(&self) -> Result<usize> {
+        // Every supported report replaces the previous one, so all but the last one are discarded.
+        let mut stored: usize = 0;
+        let mut unsupported: usize = 0;
+        loop {
+            match self.update_timeout(Duration::ZERO) {
+                Ok(0) => break,
+                Ok(_) => stored += 1,
+                Err(Error::UnsupportedReport { .. }) => unsupported += 1,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(stored.saturating_sub(1) + unsupported)
+    }
+
+    /// Block until `button` is pressed.
+    ///
+    /// Only a fresh press counts, so if the button is already held when this method is called, it
+    /// must be released and pressed again. This is useful for simple "press X to continue"
+    /// prompts:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mappings::Button, mock::MockDevice, DualSense, Mode};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// ds.set_mode(Mode::NonBlocking).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// let released = report;
+    /// report[8] |= 0b0010_0000; // Cross pressed.
+    /// let pressed = report;
+    ///
+    /// // Cross is held, released and then pressed again.
+    /// for report in [pressed, released, pressed, released] {
+    ///     device.push_report(&report);
+    /// }
+    ///
+    /// ds.wait_for_press(Button::Cross).unwrap();
+    /// assert_eq!(device.pending(), 1);
+    /// assert_eq!(ds.mode(), Mode::NonBlocking);
+    /// # }
+    /// ```
+    ///
+    /// # Blocking
+    /// The controller is temporarily set to _blocking_ mode while waiting, and the previous mode
+    /// is restored before returning, even if an error occurs.
+    ///
+    /// # Errors
+    /// Unsupported reports are skipped, while any other error stops the wait and is returned.
+    pub fn wait_for_press(&self, button: Button) -> Result<()> {
+        let previous = self.swap_mode(Mode::Blocking)?;
+
+        let mut released = false;
+        let result = loop {
+            match self.update() {
+                Ok(_) => {}
+                Err(Error::UnsupportedReport { .. }) => continue,
+                Err(err) => break Err(err),
+            }
+
+            let pressed = self.state().button(button).is_pressed();
+            if pressed && released {
+                break Ok(());
+            }
+            released = !pressed;
+        };
+
+        self.set_mode(previous)?;
+        result
+    }
+
+    /// Update the current controller state without blocking the asynchronous executor.
+    ///
+    /// This method never blocks the thread, independently of the current [`mode`]. Like
+    /// [`update`] in _blocking_ mode, it only returns after a new state is read (or an error
+    /// occurs).
+    ///
+    /// On Linux, when the controller has a file descriptor (see [`as_raw_fd`]), the task waits
+    /// on it through the runtime, waking up only when a new report arrives, so there is no extra
+    /// latency. Otherwise (e.g. with the libusb backend of HIDAPI, on other platforms or with a
+    /// mock device), it falls back to checking for new data every millisecond, yielding to the
+    /// executor in between.
+    ///
+    /// # Latency
+    /// In the fallback, a new state can take up to one extra millisecond to be noticed (on top of
+    /// the timer precision of the runtime), and an idle controller wakes the task up about a
+    /// thousand times per second. At the default poll rate of 250hz (4ms between reports) this is
+    /// usually acceptable. If the latency must be as low as possible, consider using a dedicated
+    /// thread with [`spawn_poller`] instead.
+    ///
+    /// # Panics
+    /// Panics if called outside of a Tokio runtime, or if the runtime does not have the I/O and
+    /// time drivers enabled (see `tokio::runtime::Builder::enable_all`).
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::DualSense;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    ///
+    /// ds.update_async().await.unwrap();
+    /// println!("{:?}", ds.state());
+    /// # }
+    /// ```
+    ///
+    /// [`mode`]: fn@crate::DualSense::mode
+    /// [`update`]: fn@crate::DualSense::update
+    /// [`spawn_poller`]: fn@crate::DualSense::spawn_poller
+    /// [`as_raw_fd`]: fn@crate::DualSense::as_raw_fd
+    #[cfg(feature = "tokio")]
+    pub async fn update_async(&self) -> Result<usize> {
+        const CHECK_INTERVAL: Duration = Duration::from_millis(1);
+
+        #[cfg(target_os = "linux")]
+        if let Some(fd) = self.as_raw_fd() {
+            // SAFETY: The descriptor is owned by the controller, which can only be replaced (and
+            // the descriptor closed) through a mutable reference, so it stays open while `self`
+            // is borrowed.
+            let fd = unsafe { BorrowedFd::borrow_raw(fd) };
+
+            // If the descriptor cannot be registered in the runtime, fall back to polling below.
+            if let Ok(fd) = AsyncFd::with_interest(fd, Interest::READABLE) {
+                loop {
+                    match self.update_timeout(Duration::ZERO)? {
+                        0 => {}
+                        bytes => return Ok(bytes),
+                    }
+
+                    // The readiness is cleared before reading again, so the next wait only ends
+                    // when another report arrives.
+                    let Ok(mut guard) = fd.readable().await else {
+                        break;
+                    };
+                    guard.clear_ready();
+                }
+            }
+        }
+
+        loop {
+            match self.update_timeout(Duration::ZERO)? {
+                0 => tokio::time::sleep(CHECK_INTERVAL).await,
+                bytes => return Ok(bytes),
+            }
+        }
+    }
+
+    /// Parse and store a report read from the controller.
+    fn store(&self, buffer: &[u8], bytes: usize) -> Result<usize> {
+        // The input is stored before writing the outputs, so a failed write never loses a report
+        // that was already read.
+        let stored = self.store_input(buffer, bytes);
+        self.advance_sequences();
+
+        stored
+    }
+
+    /// Parse and store the report read into `buffer`, returning the number of bytes read.
+    fn store_input(&self, buffer: &[u8], bytes: usize) -> Result<usize> {
         if bytes == 0 {
             return Ok(bytes);
         }
 
         // Guard against other types of reports, see
         // https://controllers.fandom.com/wiki/Sony_DualSense#USB for more information.
-        assert!(
-            bytes == 64,
-            "Only one type of report is currently implemented"
-        );
-        match buffer[0] {
-            0x01 => (),
-            _ => unimplemented!(),
-        }
+        let Some(report) = INPUT_REPORTS
+            .iter()
+            .find(|report| report.id == buffer[0] && report.len == bytes)
+        else {
+            return Err(Error::UnsupportedReport {
+                id: buffer[0],
+                len: bytes,
+            });
+        };
 
-        let state = DualSenseState::from(RawInputReportUSB::new(buffer));
+        let (state, raw) = (report.parse)(&buffer[..bytes], self.kind)?;
         self.state.replace(state);
+        self.report.replace(raw);
+        self.has_update.set(true);
+
+        let now = Instant::now();
+        if let Some(last) = self.last_report.replace(Some(now)) {
+            self.report_interval.set(Some(now - last));
+        }
 
         Ok(bytes)
     }
 
+    /// Update the current controller state, returning if it was actually updated.
+    ///
+    /// This method behaves exactly like [`update`], but instead of returning the number of bytes
+    /// read, it returns `true` when a new state was read and `false` otherwise. This is mostly
+    /// useful in _non-blocking_ mode, where [`update`] can return without reading anything.
+    ///
+    /// [`update`]: fn@crate::DualSense::update
+    pub fn update_checked(&self) -> Result<bool> {
+        Ok(self.update()? > 0)
+    }
+
     /// Set mode to be either _blocking_ or _non-blocking_.
     pub fn set_mode(&self, mode: Mode) -> Result<()> {
         // TODO: Understand why it can fail.
         self.controller.set_mode(mode)?;
         self.mode.replace(mode);
 
-        Ok(())
+        Ok(())
+    }
+
+    /// Set mode to be either _blocking_ or _non-blocking_, returning the previous mode.
+    ///
+    /// This is useful to temporarily change the mode and then restore it afterwards:
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{DualSense, Mode};
+    ///
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    ///
+    /// let previous = ds.swap_mode(Mode::NonBlocking).unwrap();
+    /// ds.update().unwrap();
+    /// ds.set_mode(previous).unwrap();
+    /// ```
+    ///
+    /// If setting the new mode fails, the current mode is left unchanged.
+    pub fn swap_mode(&self, mode: Mode) -> Result<Mode> {
+        let previous = self.mode();
+        self.set_mode(mode)?;
+
+        Ok(previous)
+    }
+
+    /// Reconnect to the controller after it was disconnected.
+    ///
+    /// The same controller that was originally binded is opened again (identified by its serial
+    /// number, when it reports one), and the current mode is applied to it. The controller state is
+    /// kept as is until the next [`update`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense, Error, Mode};
+    ///
+    /// let device = MockDevice::new();
+    /// let mut ds = DualSense::mock(device.clone()).unwrap();
+    /// ds.set_mode(Mode::NonBlocking).unwrap();
+    ///
+    /// device.disconnect();
+    /// assert!(matches!(ds.update(), Err(Error::Disconnected)));
+    ///
+    /// ds.reconnect().unwrap();
+    /// assert_eq!(ds.mode(), Mode::NonBlocking);
+    /// assert_eq!(ds.update().unwrap(), 0);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// If the controller is still not connected, [`Error::Bind`] is returned and the old bind is
+    /// kept, so it is possible to try reconnecting again later.
+    ///
+    /// [`update`]: fn@crate::DualSense::update
+    /// [`Error::Bind`]: crate::Error::Bind
+    pub fn reconnect(&mut self) -> Result<()> {
+        let controller = self.controller.reopen()?;
+        controller.set_mode(self.mode())?;
+        self.controller = controller;
+        // The time spent disconnected is not an interval between reports.
+        self.last_report.set(None);
+
+        Ok(())
+    }
+
+    /// Read the firmware information of the controller.
+    ///
+    /// The information is read directly from the controller (through the feature report `0x20`),
+    /// so this method does not depend on the controller state. See [`FirmwareInfo`] for the
+    /// available fields.
+    ///
+    /// [`FirmwareInfo`]: struct@crate::info::FirmwareInfo
+    pub fn firmware_info(&self) -> Result<FirmwareInfo> {
+        let mut buffer = [0_u8; 64];
+        buffer[0] = FirmwareInfo::REPORT_ID;
+        let bytes = self.controller.get_feature_report(&mut buffer)?;
+
+        FirmwareInfo::from_raw(&buffer[..bytes])
+    }
+
+    /// Read the strings describing the controller, such as its product name.
+    ///
+    /// See [`DeviceStrings`] for the available strings.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let ds = DualSense::mock(MockDevice::new()).unwrap();
+    /// let info = ds.device_info().unwrap();
+    ///
+    /// assert_eq!(info.manufacturer(), "Sony Interactive Entertainment");
+    /// assert_eq!(info.product(), "DualSense Wireless Controller");
+    /// # }
+    /// ```
+    ///
+    /// [`DeviceStrings`]: struct@crate::info::DeviceStrings
+    pub fn device_info(&self) -> Result<DeviceStrings> {
+        Ok(DeviceStrings {
+            manufacturer: self.controller.get_string(DeviceString::Manufacturer)?,
+            product: self.controller.get_string(DeviceString::Product)?,
+            serial_number: self.controller.get_string(DeviceString::SerialNumber)?,
+        })
+    }
+
+    /// Read the motion sensors calibration of the controller.
+    ///
+    /// The calibration is read directly from the controller (through the feature report `0x05`),
+    /// so it only needs to be read once. See [`Calibration`] for how to apply it.
+    ///
+    /// [`Calibration`]: struct@crate::info::Calibration
+    pub fn read_calibration(&self) -> Result<Calibration> {
+        let mut buffer = [0_u8; 64];
+        buffer[0] = Calibration::REPORT_ID;
+        let bytes = self.controller.get_feature_report(&mut buffer)?;
+
+        Calibration::from_raw(&buffer[..bytes])
+    }
+
+    /// Read the pairing information of the controller.
+    ///
+    /// The information is read directly from the controller (through the feature report `0x09`).
+    /// Since the controller address never changes, it can be used to recognize a specific
+    /// controller independently of the order they were connected. See [`PairingInfo`] for the
+    /// available fields.
+    ///
+    /// [`PairingInfo`]: struct@crate::info::PairingInfo
+    pub fn pairing_info(&self) -> Result<PairingInfo> {
+        let mut buffer = [0_u8; 64];
+        buffer[0] = PairingInfo::REPORT_ID;
+        let bytes = self.controller.get_feature_report(&mut buffer)?;
+
+        PairingInfo::from_raw(&buffer[..bytes])
+    }
+
+    /// Get the model of the binded controller.
+    pub fn kind(&self) -> KnownController {
+        self.kind
+    }
+
+    /// Get the [SDL] `GameControllerDB` mapping line of the binded controller.
+    ///
+    /// See [`KnownController::sdl_mapping`] for more information.
+    ///
+    /// [SDL]: https://github.com/mdqinc/SDL_GameControllerDB
+    /// [`KnownController::sdl_mapping`]: fn@crate::KnownController::sdl_mapping
+    pub fn sdl_mapping(&self) -> String {
+        self.kind.sdl_mapping()
+    }
+
+    /// Get the current mode.
+    pub fn mode(&self) -> Mode {
+        self.mode.get()
+    }
+
+    /// Get the current controller state.
+    ///
+    /// If [`update`] was not called at least once, the returned state is not representative of the
+    /// controller *actual* state. Instead, every input is at rest, and the state is marked as
+    /// uninitialized (see [`DualSenseState::is_initialized`]). As such, this method should be
+    /// called *only* after at least one update from the controller, which can be checked with
+    /// [`has_update`].
+    ///
+    /// [`update`]: fn@crate::DualSense::update
+    /// [`DualSenseState::is_initialized`]: fn@crate::DualSenseState::is_initialized
+    /// [`has_update`]: fn@crate::DualSense::has_update
+    pub fn state(&self) -> DualSenseState {
+        self.state.get()
+    }
+
+    /// Return `true` if a state was ever read from the controller and `false` otherwise.
+    ///
+    /// This becomes `true` after the first update that reads a new state, and stays `true`
+    /// afterwards (even after a [`reconnect`]). Until then, [`state`] returns an uninitialized
+    /// state that should not be acted upon.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense, Mode};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// ds.set_mode(Mode::NonBlocking).unwrap();
+    ///
+    /// assert_eq!(ds.update().unwrap(), 0);
+    /// assert!(!ds.has_update());
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// ds.update().unwrap();
+    ///
+    /// assert!(ds.has_update());
+    /// # }
+    /// ```
+    ///
+    /// [`reconnect`]: fn@crate::DualSense::reconnect
+    /// [`state`]: fn@crate::DualSense::state
+    pub fn has_update(&self) -> bool {
+        self.has_update.get()
+    }
+
+    /// Get an iterator that updates the controller and yields every new state.
+    ///
+    /// Each call to [`next`] blocks until a new state is read, independently of the current
+    /// [`mode`], waiting for the controller with [`update_timeout`] instead of polling it. Reports
+    /// not supported by the crate are skipped, while any other error (e.g. a disconnection) is
+    /// yielded and then ends the iteration.
+    ///
+    /// If `dedup` is `true`, states whose inputs did not change since the last yielded state are
+    /// skipped. The motion sensors and the timestamps are ignored in this comparison, since they
+    /// change in virtually every report. As such, states that only differ in motion are skipped
+    /// too, so `dedup` should be `false` when the motion sensors are used.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense, Error};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// report[16] = 0xFF; // Only the gyroscope changed.
+    /// device.push_report(&report);
+    /// report[8] |= 0b0010_0000; // Cross pressed.
+    /// device.push_report(&report);
+    ///
+    /// let mut events = ds.events(true);
+    /// assert!(!events.next().unwrap().unwrap().cross().is_pressed());
+    /// assert!(events.next().unwrap().unwrap().cross().is_pressed());
+    ///
+    /// device.disconnect();
+    /// assert!(matches!(events.next(), Some(Err(Error::Disconnected))));
+    /// assert!(events.next().is_none());
+    /// # }
+    /// ```
+    ///
+    /// [`next`]: fn@Iterator::next
+    /// [`mode`]: fn@crate::DualSense::mode
+    /// [`update_timeout`]: fn@crate::DualSense::update_timeout
+    pub fn events(&self, dedup: bool) -> impl Iterator<Item = Result<DualSenseState>> + '_ {
+        // How long each read waits for a report, so waiting for one doesn't spin.
+        const TIMEOUT: Duration = Duration::from_millis(100);
+
+        let mut previous: Option<DualSenseState> = None;
+        let mut ended = false;
+
+        std::iter::from_fn(move || loop {
+            if ended {
+                return None;
+            }
+
+            match self.update_timeout(TIMEOUT) {
+                Ok(0) | Err(Error::UnsupportedReport { .. }) => continue,
+                Ok(_) => {}
+                Err(err) => {
+                    ended = true;
+                    return Some(Err(err));
+                }
+            }
+
+            let state = self.state();
+            if dedup && previous.is_some_and(|previous| previous.same_inputs(&state)) {
+                continue;
+            }
+
+            previous = Some(state);
+            return Some(Ok(state));
+        })
+    }
+
+    /// Set the intensity of the rumble motors.
+    ///
+    /// The left motor is the heavier one, producing a low frequency rumble, while the right one
+    /// produces a high frequency rumble. An intensity of `0` stops the motor. Calling this method
+    /// also stops any pattern being played by [`play_rumble_pattern`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// ds.set_rumble(255, 64).unwrap();
+    ///
+    /// let written = device.written();
+    /// assert_eq!(written[0][0], 0x02);
+    /// assert_eq!(written[0][3..=4], [64, 255]);
+    /// # }
+    /// ```
+    ///
+    /// [`play_rumble_pattern`]: fn@crate::DualSense::play_rumble_pattern
+    pub fn set_rumble(&self, left: u8, right: u8) -> Result<()> {
+        self.rumble_pattern.replace(None);
+        self.write_rumble(left, right)
+    }
+
+    /// Set the scale applied to the intensity of the left and right rumble motors, respectively.
+    ///
+    /// Every rumble written to the controller (by [`set_rumble`], [`set_outputs`] or a pattern)
+    /// has its intensities multiplied by the scales, clamped from `0` to `255`. This makes it
+    /// possible to globally reduce the rumble strength (e.g. as an accessibility setting), or to
+    /// compensate for motors that feel different, without touching every call site. The scales
+    /// are `1.0` by default, which leaves the intensities unchanged.
+    ///
+    /// The scales only apply to the next rumble written, so to apply them to the current one, call
+    /// [`refresh_outputs`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// ds.set_rumble_scale(0.5, 2.0);
+    /// ds.set_rumble(200, 100).unwrap();
+    ///
+    /// let written = device.written();
+    /// assert_eq!(written[0][3..=4], [200, 100]);
+    /// # }
+    /// ```
+    ///
+    /// [`set_rumble`]: fn@crate::DualSense::set_rumble
+    /// [`set_outputs`]: fn@crate::DualSense::set_outputs
+    /// [`refresh_outputs`]: fn@crate::DualSense::refresh_outputs
+    pub fn set_rumble_scale(&self, left_scale: f32, right_scale: f32) {
+        self.rumble_scale.set((left_scale, right_scale));
+    }
+
+    /// Get the scale applied to the intensity of the left and right rumble motors, respectively.
+    ///
+    /// See [`set_rumble_scale`] for more information.
+    ///
+    /// [`set_rumble_scale`]: fn@crate::DualSense::set_rumble_scale
+    pub fn rumble_scale(&self) -> (f32, f32) {
+        self.rumble_scale.get()
+    }
+
+    /// Set the color of the lightbar.
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{output::Color, DualSense};
+    ///
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    ///
+    /// ds.set_lightbar(Color::from_hex(0xFF8800)).unwrap();
+    /// ```
+    pub fn set_lightbar(&self, color: Color) -> Result<()> {
+        self.lightbar_fade.replace(None);
+        self.write_lightbar(color)
+    }
+
+    /// Set the player LEDs below the touchpad.
+    ///
+    /// The LEDs are set one bit per LED from left to right (`0b10000` is the leftmost one), and
+    /// only the 5 lowest bits are used. To show a player number the same way the console does, use
+    /// the patterns in [`PLAYER_LEDS`]:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, output::PLAYER_LEDS, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// assert_eq!(ds.player_slot(), None);
+    ///
+    /// ds.set_player_leds(PLAYER_LEDS[1]).unwrap();
+    ///
+    /// assert_eq!(device.written()[0][44], 0b01010);
+    /// assert_eq!(ds.player_leds(), Some(0b01010));
+    /// assert_eq!(ds.player_slot(), Some(2));
+    /// # }
+    /// ```
+    ///
+    /// [`PLAYER_LEDS`]: crate::output::PLAYER_LEDS
+    pub fn set_player_leds(&self, leds: u8) -> Result<()> {
+        self.write_outputs(&Outputs {
+            player_leds: Some(leds & 0b0001_1111),
+            ..Outputs::default()
+        })
+    }
+
+    /// Get the last player LEDs set, either by [`set_player_leds`] or by [`set_outputs`].
+    ///
+    /// The controller does not report its player LEDs back, so this is the last value written to
+    /// it, and `None` if they were never set through this bind.
+    ///
+    /// [`set_player_leds`]: fn@crate::DualSense::set_player_leds
+    /// [`set_outputs`]: fn@crate::DualSense::set_outputs
+    pub fn player_leds(&self) -> Option<u8> {
+        self.outputs
+            .get()
+            .player_leds
+            .map(|leds| leds & 0b0001_1111)
+    }
+
+    /// Get the player number (from `1` to `5`) shown by the player LEDs.
+    ///
+    /// Since the player LEDs are not part of the input report, there is no player number in
+    /// [`DualSenseState`]. Instead, the number is read back from the last LEDs set (see
+    /// [`player_leds`]), so `None` is returned if they were never set or if they do not match any
+    /// of the [`PLAYER_LEDS`] patterns.
+    ///
+    /// [`DualSenseState`]: struct@crate::DualSenseState
+    /// [`player_leds`]: fn@crate::DualSense::player_leds
+    /// [`PLAYER_LEDS`]: crate::output::PLAYER_LEDS
+    pub fn player_slot(&self) -> Option<u8> {
+        let leds = self.player_leds()?;
+
+        PLAYER_LEDS
+            .iter()
+            .position(|&pattern| pattern == leds)
+            .map(|index| index as u8 + 1)
+    }
+
+    /// Set the effect of a back trigger, validating its parameters.
+    ///
+    /// Unlike [`set_outputs`], which writes the effect as it is, this method checks the
+    /// parameters first. Parameters out of their range are clamped to it, while combinations that
+    /// make no sense are rejected. The accepted ranges of each effect are:
+    ///
+    /// | Effect      | Parameter   | Range              | Out of range           |
+    /// |-------------|-------------|--------------------|------------------------|
+    /// | `Feedback`  | `position`  | `0` to `9`         | Clamped                |
+    /// | `Feedback`  | `strength`  | `1` to `8`         | `0` is rejected        |
+    /// | `Weapon`    | `start`     | `2` to `7`         | Clamped                |
+    /// | `Weapon`    | `end`       | `start + 1` to `8` | Rejected if `<= start` |
+    /// | `Weapon`    | `strength`  | `1` to `8`         | `0` is rejected        |
+    /// | `Vibration` | `position`  | `0` to `9`         | Clamped                |
+    /// | `Vibration` | `amplitude` | `1` to `8`         | `0` is rejected        |
+    /// | `Vibration` | `frequency` | `1` to `255`       | `0` is rejected        |
+    ///
+    /// Strengths and amplitudes above `8` are clamped to `8`. To turn an effect off, use
+    /// [`TriggerEffect::Off`] instead of a strength of `0`.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::output::{TriggerEffect, TriggerSide};
+    /// use duplosentido::{mock::MockDevice, DualSense, Error};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// let invalid = |effect| {
+    ///     let result = ds.set_trigger_effect(TriggerSide::Left, effect);
+    ///     matches!(result, Err(Error::InvalidTriggerEffect(_)))
+    /// };
+    ///
+    /// // Soft limits are clamped.
+    /// let feedback = TriggerEffect::Feedback { position: 20, strength: 20 };
+    /// ds.set_trigger_effect(TriggerSide::Left, feedback).unwrap();
+    /// assert_eq!(device.written().len(), 1);
+    ///
+    /// // Invalid combinations are rejected, without writing anything.
+    /// assert!(invalid(TriggerEffect::Feedback { position: 0, strength: 0 }));
+    /// assert!(invalid(TriggerEffect::Weapon { start: 4, end: 4, strength: 8 }));
+    /// assert!(invalid(TriggerEffect::Weapon { start: 6, end: 3, strength: 8 }));
+    /// assert!(invalid(TriggerEffect::Weapon { start: 2, end: 6, strength: 0 }));
+    /// assert!(invalid(TriggerEffect::Vibration { position: 0, amplitude: 0, frequency: 40 }));
+    /// assert!(invalid(TriggerEffect::Vibration { position: 0, amplitude: 8, frequency: 0 }));
+    /// assert_eq!(device.written().len(), 1);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// If the parameters of `effect` are invalid, [`Error::InvalidTriggerEffect`] is returned and
+    /// nothing is written to the controller.
+    ///
+    /// [`set_outputs`]: fn@crate::DualSense::set_outputs
+    /// [`TriggerEffect::Off`]: crate::output::TriggerEffect::Off
+    /// [`Error::InvalidTriggerEffect`]: crate::Error::InvalidTriggerEffect
+    pub fn set_trigger_effect(&self, side: TriggerSide, effect: TriggerEffect) -> Result<()> {
+        let effect = effect.validate()?;
+
+        self.write_trigger(side, effect)
+    }
+
+    /// Make a back trigger vibrate, as a haptic channel separate from the rumble motors.
+    ///
+    /// The vibration is applied along the whole trigger travel, through the
+    /// [`TriggerEffect::Vibration`] effect, so it replaces any other effect of the trigger. The
+    /// controller accepts an `amplitude` from `1` to `8` (higher values are clamped to `8`). The
+    /// `frequency` is in hertz, with noticeable vibrations usually ranging from about `20` to
+    /// `200`. Since neither can vibrate the trigger, an `amplitude` or a `frequency` of `0` stops
+    /// the vibration, turning the effect off, instead of being rejected like by
+    /// [`set_trigger_effect`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, output::TriggerSide, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// ds.set_trigger_rumble(TriggerSide::Right, 8, 40).unwrap();
+    /// ds.set_trigger_rumble(TriggerSide::Right, 0, 40).unwrap();
+    ///
+    /// let written = device.written();
+    /// assert_eq!(written[0][11], 0x26); // Vibration.
+    /// assert_eq!(written[0][20], 40);
+    /// assert_eq!(written[1][11], 0x05); // Off.
+    ///
+    /// ds.set_trigger_rumble(TriggerSide::Right, 8, 0).unwrap();
+    /// assert_eq!(device.written()[2][11], 0x05); // Off.
+    /// # }
+    /// ```
+    ///
+    /// [`TriggerEffect::Vibration`]: crate::output::TriggerEffect::Vibration
+    /// [`set_trigger_effect`]: fn@crate::DualSense::set_trigger_effect
+    pub fn set_trigger_rumble(
+        &self,
+        side: TriggerSide,
+        amplitude: u8,
+        frequency: u8,
+    ) -> Result<()> {
+        let effect = match (amplitude, frequency) {
+            (0, _) | (_, 0) => TriggerEffect::Off,
+            (amplitude, frequency) => TriggerEffect::Vibration {
+                position: 0,
+                amplitude,
+                frequency,
+            },
+        };
+
+        // The effect is always valid, but validating it also clamps the amplitude.
+        self.set_trigger_effect(side, effect)
+    }
+
+    /// Mute or unmute the microphone of the controller.
+    ///
+    /// Unlike [`Outputs::mic_led`], which only changes the LED of the mute button, this method
+    /// actually mutes the microphone. The resulting state is reported by
+    /// [`MicrophoneState::is_muted`], once read from the controller.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// ds.set_mic_muted(true).unwrap();
+    /// ds.set_mic_muted(false).unwrap();
+    ///
+    /// let written = device.written();
+    /// assert_eq!(written[0][2] & 0x02, 0x02);
+    /// assert_eq!(written[0][10] & 0x10, 0x10);
+    /// assert_eq!(written[1][2] & 0x02, 0x02);
+    /// assert_eq!(written[1][10] & 0x10, 0);
+    /// # }
+    /// ```
+    ///
+    /// [`Outputs::mic_led`]: crate::output::Outputs::mic_led
+    /// [`MicrophoneState::is_muted`]: fn@crate::mappings::MicrophoneState::is_muted
+    pub fn set_mic_muted(&self, muted: bool) -> Result<()> {
+        self.write_outputs(&Outputs {
+            mic_muted: Some(muted),
+            ..Outputs::default()
+        })
+    }
+
+    /// Set multiple outputs of the controller at once.
+    ///
+    /// Every output set in `outputs` is written in a single report, while the others are left
+    /// untouched. See [`Outputs`] for the available outputs. If the rumble is set, any pattern
+    /// being played by [`play_rumble_pattern`] is stopped, and if the lightbar is set, any fade
+    /// started by [`fade_lightbar`] is stopped.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::output::{Color, Outputs};
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// ds.set_outputs(&Outputs {
+    ///     rumble: Some((255, 0)),
+    ///     lightbar: Some(Color::BLUE),
+    ///     ..Outputs::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let written = device.written();
+    /// assert_eq!(written.len(), 1);
+    /// assert_eq!(written[0][4], 255);
+    /// assert_eq!(written[0][45..48], [0, 0, 255]);
+    /// # }
+    /// ```
+    ///
+    /// [`Outputs`]: struct@crate::output::Outputs
+    /// [`play_rumble_pattern`]: fn@crate::DualSense::play_rumble_pattern
+    /// [`fade_lightbar`]: fn@crate::DualSense::fade_lightbar
+    pub fn set_outputs(&self, outputs: &Outputs) -> Result<()> {
+        if outputs.rumble.is_some() {
+            self.rumble_pattern.replace(None);
+        }
+        if outputs.lightbar.is_some() {
+            self.lightbar_fade.replace(None);
+        }
+
+        self.write_outputs(outputs)
+    }
+
+    /// Write the last applied outputs again.
+    ///
+    /// The outputs of the controller may be reset by the operating system or after being idle for
+    /// a while, so it is common to refresh them periodically (e.g. once per second in a game loop).
+    /// Every output ever set, either by [`set_outputs`] or by the other output methods, is written
+    /// again in a single report. Outputs never set are left untouched.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, output::Color, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// ds.set_lightbar(Color::GREEN).unwrap();
+    /// ds.set_rumble(0, 32).unwrap();
+    /// ds.refresh_outputs().unwrap();
+    ///
+    /// let written = device.written();
+    /// assert_eq!(written.len(), 3);
+    /// assert_eq!(written[2][3], 32);
+    /// assert_eq!(written[2][45..48], [0, 255, 0]);
+    /// # }
+    /// ```
+    ///
+    /// [`set_outputs`]: fn@crate::DualSense::set_outputs
+    pub fn refresh_outputs(&self) -> Result<()> {
+        self.write_outputs(&self.outputs.get())
+    }
+
+    /// Write the `outputs` set, remembering them as the last applied ones.
+    ///
+    /// The rumble is scaled when written, but remembered unscaled, so the scale can be changed
+    /// later.
+    fn write_outputs(&self, outputs: &Outputs) -> Result<()> {
+        let (left_scale, right_scale) = self.rumble_scale.get();
+        let scale = |value: u8, scale: f32| (value as f32 * scale).round().clamp(0.0, 255.0) as u8;
+        let scaled = Outputs {
+            rumble: outputs
+                .rumble
+                .map(|(left, right)| (scale(left, left_scale), scale(right, right_scale))),
+            ..*outputs
+        };
+
+        self.controller
+            .write(OutputReport::from(&scaled).as_array())?;
+
+        let mut applied = self.outputs.get();
+        applied.merge(outputs);
+        self.outputs.set(applied);
+
+        Ok(())
+    }
+
+    /// Start playing a rumble pattern.
+    ///
+    /// Each step of the `pattern` sets the intensity of the motors for its duration, and the
+    /// motors are stopped after the last step. This makes it possible to reuse effects, such as a
+    /// heartbeat:
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{output::RumbleStep, DualSense};
+    /// use std::time::Duration;
+    ///
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    ///
+    /// let beat = |left, duration| RumbleStep {
+    ///     left,
+    ///     right: 0,
+    ///     duration: Duration::from_millis(duration),
+    /// };
+    /// ds.play_rumble_pattern(&[beat(200, 100), beat(0, 100), beat(255, 150), beat(0, 600)])
+    ///     .unwrap();
+    ///
+    /// loop {
+    ///     // The pattern advances while the controller is updated.
+    ///     ds.update().unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Blocking
+    /// This method does not block. It writes the first step immediately, and the following steps
+    /// are written by [`update`] (and the other update methods) once they are due. As such, the
+    /// pattern is only as precise as the rate in which the controller is updated.
+    ///
+    /// # Cancelling
+    /// Playing another pattern replaces the current one, while calling [`set_rumble`] stops it.
+    ///
+    /// # Errors
+    /// If writing the first step fails, its error is returned. If writing one of the following
+    /// steps fails, the pattern is stopped and the error is returned by the next call to
+    /// [`update`], before anything is read. As such, the report read by the update that advanced
+    /// the pattern is never lost.
+    ///
+    /// [`update`]: fn@crate::DualSense::update
+    /// [`set_rumble`]: fn@crate::DualSense::set_rumble
+    pub fn play_rumble_pattern(&self, pattern: &[RumbleStep]) -> Result<()> {
+        let steps = pattern
+            .iter()
+            .map(|step| ((step.left, step.right), step.duration))
+            .collect();
+
+        match Sequence::start(steps, (0, 0)) {
+            Some((pattern, (left, right))) => {
+                self.rumble_pattern.replace(Some(pattern));
+                self.write_rumble(left, right)
+            }
+            None => self.set_rumble(0, 0),
+        }
+    }
+
+    /// Fade the lightbar from one color to another.
+    ///
+    /// The fade is divided in `steps` colors, interpolated from `from` to `to`, each one lasting
+    /// for an equal part of the `duration`. Once the `duration` elapses, the lightbar is left with
+    /// the `to` color. Fading back and forth makes the lightbar "breathe":
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{output::Color, DualSense};
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    /// let period = Duration::from_secs(2);
+    ///
+    /// let mut last = Instant::now() - period;
+    /// let mut colors = (Color::OFF, Color::BLUE);
+    /// loop {
+    ///     if last.elapsed() >= period {
+    ///         ds.fade_lightbar(colors.0, colors.1, period, 50).unwrap();
+    ///         colors = (colors.1, colors.0);
+    ///         last = Instant::now();
+    ///     }
+    ///
+    ///     // The fade advances while the controller is updated.
+    ///     ds.update().unwrap();
+    /// }
+    /// ```
+    ///
+    /// A `steps` of `0` is treated as `1`, setting the `from` color for the whole `duration`.
+    ///
+    /// # Blocking
+    /// Just like [`play_rumble_pattern`], this method does not block. It writes the `from` color
+    /// immediately, and the following colors are written by [`update`] (and the other update
+    /// methods) once they are due.
+    ///
+    /// # Cancelling
+    /// Starting another fade replaces the current one, while calling [`set_lightbar`] (or
+    /// [`set_outputs`] with a lightbar color) stops it, leaving the lightbar with the new color.
+    ///
+    /// # Errors
+    /// Just like with [`play_rumble_pattern`], a failed write of one of the following colors stops
+    /// the fade, and its error is returned by the next call to [`update`] instead of the one that
+    /// read a report.
+    ///
+    /// [`play_rumble_pattern`]: fn@crate::DualSense::play_rumble_pattern
+    /// [`update`]: fn@crate::DualSense::update
+    /// [`set_lightbar`]: fn@crate::DualSense::set_lightbar
+    /// [`set_outputs`]: fn@crate::DualSense::set_outputs
+    pub fn fade_lightbar(
+        &self,
+        from: Color,
+        to: Color,
+        duration: Duration,
+        steps: u32,
+    ) -> Result<()> {
+        let steps = output::fade(from, to, duration, steps);
+
+        // There is always at least one step, so the fade always starts.
+        if let Some((fade, first)) = Sequence::start(steps, to) {
+            self.lightbar_fade.replace(Some(fade));
+            self.write_lightbar(first)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the next values of the sequences being played, if they are due.
+    ///
+    /// If a write fails, its sequence is stopped and the error is kept, to be returned by the next
+    /// update before anything is read (see [`take_output_error`]).
+    ///
+    /// [`take_output_error`]: fn@Self::take_output_error
+    fn advance_sequences(&self) {
+        if let Some((left, right)) = output::advance_sequence(&self.rumble_pattern) {
+            if let Err(err) = self.write_rumble(left, right) {
+                self.rumble_pattern.replace(None);
+                self.output_error.replace(Some(err));
+            }
+        }
+        if let Some(color) = output::advance_sequence(&self.lightbar_fade) {
+            if let Err(err) = self.write_lightbar(color) {
+                self.lightbar_fade.replace(None);
+                self.output_error.replace(Some(err));
+            }
+        }
+    }
+
+    /// Return the error of the last failed sequence write, if there is one, clearing it.
+    fn take_output_error(&self) -> Result<()> {
+        match self.output_error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Write the color of the lightbar.
+    fn write_lightbar(&self, color: Color) -> Result<()> {
+        self.write_outputs(&Outputs {
+            lightbar: Some(color),
+            ..Outputs::default()
+        })
+    }
+
+    /// Write the effect of the back trigger on `side`.
+    fn write_trigger(&self, side: TriggerSide, effect: TriggerEffect) -> Result<()> {
+        let outputs = match side {
+            TriggerSide::Left => Outputs {
+                left_trigger: Some(effect),
+                ..Outputs::default()
+            },
+            TriggerSide::Right => Outputs {
+                right_trigger: Some(effect),
+                ..Outputs::default()
+            },
+        };
+
+        self.write_outputs(&outputs)
+    }
+
+    /// Write the intensity of the rumble motors.
+    fn write_rumble(&self, left: u8, right: u8) -> Result<()> {
+        self.write_outputs(&Outputs {
+            rumble: Some((left, right)),
+            ..Outputs::default()
+        })
+    }
+
+    /// Set the radial deadzone applied to the normalized analog sticks.
+    ///
+    /// The deadzone only affects [`left_stick_normalized`] and [`right_stick_normalized`]. The
+    /// analog sticks in [`state`] are left untouched, so the raw coordinates are still available.
+    /// The `deadzone` is clamped from `0.0` to `0.9`, and it is `0.0` by default.
+    ///
+    /// [`left_stick_normalized`]: fn@crate::DualSense::left_stick_normalized
+    /// [`right_stick_normalized`]: fn@crate::DualSense::right_stick_normalized
+    /// [`state`]: fn@crate::DualSense::state
+    pub fn set_stick_deadzone(&self, deadzone: f32) {
+        self.stick_deadzone.set(deadzone.clamp(0.0, 0.9));
+    }
+
+    /// Get the radial deadzone applied to the normalized analog sticks.
+    pub fn stick_deadzone(&self) -> f32 {
+        self.stick_deadzone.get()
+    }
+
+    /// Learn the resting position of the analog sticks.
+    ///
+    /// The controller is updated until `samples` states are read, which are then used to build a
+    /// [`StickCalibration`]. The sticks must not be touched meanwhile. The calibration is only
+    /// returned, so it can be stored and reused; to apply it, use [`set_stick_calibration`]:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// // The left stick rests slightly to the right.
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[1..=4].copy_from_slice(&[135, 128, 128, 128]);
+    /// report[8] = 0x08;
+    /// for _ in 0..10 {
+    ///     device.push_report(&report);
+    /// }
+    ///
+    /// let calibration = ds.calibrate_sticks(10).unwrap();
+    /// ds.set_stick_calibration(calibration);
+    ///
+    /// let (x, y) = ds.left_stick_normalized();
+    /// assert!(x.abs() < 1e-6 && y.abs() < 1e-6);
+    /// # }
+    /// ```
+    ///
+    /// # Blocking
+    /// This method reads the states with [`update_timeout`], waiting for each one independently
+    /// of the current [`mode`], so it takes about 4ms per sample.
+    ///
+    /// # Errors
+    /// Unsupported reports are skipped, while any other error stops the calibration and is
+    /// returned.
+    ///
+    /// [`StickCalibration`]: struct@crate::mappings::StickCalibration
+    /// [`set_stick_calibration`]: fn@crate::DualSense::set_stick_calibration
+    /// [`update_timeout`]: fn@crate::DualSense::update_timeout
+    /// [`mode`]: fn@crate::DualSense::mode
+    pub fn calibrate_sticks(&self, samples: usize) -> Result<StickCalibration> {
+        // How long each read waits for a report, so waiting for one doesn't spin.
+        const TIMEOUT: Duration = Duration::from_millis(100);
+
+        let mut states = Vec::with_capacity(samples);
+        while states.len() < samples {
+            match self.update_timeout(TIMEOUT) {
+                Ok(0) | Err(Error::UnsupportedReport { .. }) => continue,
+                Ok(_) => states.push(self.state()),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(StickCalibration::from_samples(&states))
+    }
+
+    /// Set the resting position of the analog sticks, subtracted from the normalized sticks.
+    ///
+    /// The calibration only affects [`left_stick_normalized`] and [`right_stick_normalized`], and
+    /// it is applied before the deadzone. By default, the sticks are assumed to rest at the center.
+    ///
+    /// [`left_stick_normalized`]: fn@crate::DualSense::left_stick_normalized
+    /// [`right_stick_normalized`]: fn@crate::DualSense::right_stick_normalized
+    pub fn set_stick_calibration(&self, calibration: StickCalibration) {
+        self.stick_calibration.set(calibration);
+    }
+
+    /// Get the resting position of the analog sticks.
+    pub fn stick_calibration(&self) -> StickCalibration {
+        self.stick_calibration.get()
+    }
+
+    /// Set if the `Y` axis of the normalized analog sticks points down instead of up.
+    ///
+    /// By default, the `Y` axis points up, following the same convention as
+    /// [`DPadDirection::as_vector`]. Engines whose `Y` axis points down can invert it here, so
+    /// both sticks are flipped consistently. The setting only affects [`left_stick_normalized`]
+    /// and [`right_stick_normalized`]; the states returned by [`state`] are left untouched.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[1..=4].copy_from_slice(&[128, 0, 128, 255]);
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// ds.update().unwrap();
+    ///
+    /// assert_eq!(ds.left_stick_normalized(), (0.0, 1.0));
+    ///
+    /// ds.set_stick_y_invert(true);
+    /// assert_eq!(ds.left_stick_normalized(), (0.0, -1.0));
+    /// assert_eq!(ds.right_stick_normalized(), (0.0, 1.0));
+    /// # }
+    /// ```
+    ///
+    /// [`DPadDirection::as_vector`]: fn@crate::mappings::DPadDirection::as_vector
+    /// [`left_stick_normalized`]: fn@crate::DualSense::left_stick_normalized
+    /// [`right_stick_normalized`]: fn@crate::DualSense::right_stick_normalized
+    /// [`state`]: fn@crate::DualSense::state
+    pub fn set_stick_y_invert(&self, invert: bool) {
+        self.stick_y_invert.set(invert);
+    }
+
+    /// Return `true` if the `Y` axis of the normalized analog sticks points down and `false`
+    /// otherwise.
+    pub fn stick_y_invert(&self) -> bool {
+        self.stick_y_invert.get()
+    }
+
+    /// Get the left analog stick coordinates normalized, with the controller deadzone applied.
+    ///
+    /// The coordinates range from `-1.0` to `1.0`, with `Y` pointing up (unless
+    /// [inverted][`set_stick_y_invert`]). The [stick calibration]
+    /// is subtracted first, and then the deadzone is applied as described in
+    /// [`StickState::with_deadzone`].
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// ds.set_stick_deadzone(0.2);
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[1..=4].copy_from_slice(&[150, 128, 128, 128]);
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// ds.update().unwrap();
+    ///
+    /// assert_eq!(ds.state().left_stick().x(), 150);
+    /// assert_eq!(ds.left_stick_normalized(), (0.0, 0.0));
+    /// # }
+    /// ```
+    ///
+    /// [`set_stick_y_invert`]: fn@crate::DualSense::set_stick_y_invert
+    /// [stick calibration]: fn@crate::DualSense::set_stick_calibration
+    /// [`StickState::with_deadzone`]: fn@crate::mappings::StickState::with_deadzone
+    pub fn left_stick_normalized(&self) -> (f32, f32) {
+        let offset = self.stick_calibration().left();
+
+        self.normalize_stick(self.state().left_stick(), offset)
+    }
+
+    /// Get the right analog stick coordinates normalized, with the controller deadzone applied.
+    ///
+    /// See [`left_stick_normalized`] for more information.
+    ///
+    /// [`left_stick_normalized`]: fn@crate::DualSense::left_stick_normalized
+    pub fn right_stick_normalized(&self) -> (f32, f32) {
+        let offset = self.stick_calibration().right();
+
+        self.normalize_stick(self.state().right_stick(), offset)
+    }
+
+    /// Normalize the `stick`, subtracting its resting `offset` and applying the deadzone.
+    fn normalize_stick(&self, stick: StickState, (x, y): (f32, f32)) -> (f32, f32) {
+        let x = (stick.x_normalized() - x).clamp(-1.0, 1.0);
+        let y = (stick.y_normalized() - y).clamp(-1.0, 1.0);
+        let (x, y) = apply_deadzone(x, y, self.stick_deadzone());
+
+        match self.stick_y_invert() {
+            true => (x, -y),
+            false => (x, y),
+        }
+    }
+
+    /// Learn the maximum axis of the back triggers.
+    ///
+    /// The controller is updated until both back triggers are pulled and then released, which
+    /// is meant to be used with a "pull both triggers fully" prompt. The maximum axis seen is used
+    /// to build a [`TriggerCalibration`]. Worn triggers may not rest exactly at zero, so an axis
+    /// of up to `16` counts as released. If the triggers are not pulled and released before the
+    /// `timeout` elapses, `None` is returned instead.
+    ///
+    /// The calibration is only returned, so it can be stored and reused; to apply it, use
+    /// [`set_trigger_calibration`]:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    /// use std::time::Duration;
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    /// let timeout = Duration::from_millis(100);
+    ///
+    /// // Both triggers are worn, so they can't be fully pulled and don't fully rest.
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// for axes in [[100, 120], [220, 240], [2, 1]] {
+    ///     report[5..=6].copy_from_slice(&axes);
+    ///     device.push_report(&report);
+    /// }
+    ///
+    /// let calibration = ds.calibrate_triggers(timeout).unwrap().expect("Both were released");
+    /// assert_eq!((calibration.l2_max(), calibration.r2_max()), (220, 240));
+    /// assert!(ds.calibrate_triggers(timeout).unwrap().is_none());
+    ///
+    /// ds.set_trigger_calibration(calibration);
+    /// report[5..=6].copy_from_slice(&[220, 120]);
+    /// device.push_report(&report);
+    /// ds.update().unwrap();
+    /// assert_eq!(ds.l2_normalized(), 1.0);
+    /// assert_eq!(ds.r2_normalized(), 0.5);
+    /// # }
+    /// ```
+    ///
+    /// # Blocking
+    /// This method reads the states with [`update_timeout`], independently of the current
+    /// [`mode`], so it blocks until both triggers are pulled and released, or until the
+    /// `timeout` elapses.
+    ///
+    /// # Errors
+    /// Unsupported reports are skipped, while any other error stops the calibration and is
+    /// returned.
+    ///
+    /// [`TriggerCalibration`]: struct@crate::mappings::TriggerCalibration
+    /// [`set_trigger_calibration`]: fn@crate::DualSense::set_trigger_calibration
+    /// [`update_timeout`]: fn@crate::DualSense::update_timeout
+    /// [`mode`]: fn@crate::DualSense::mode
+    pub fn calibrate_triggers(&self, timeout: Duration) -> Result<Option<TriggerCalibration>> {
+        // How long each read waits for a report at most, so waiting for one doesn't spin.
+        const READ_TIMEOUT: Duration = Duration::from_millis(100);
+        // The highest axis of a released trigger.
+        const RELEASED: u8 = 16;
+
+        // A timeout too large to be represented never elapses.
+        let deadline = Instant::now().checked_add(timeout);
+        let mut pulled = Vec::new();
+        let (mut l2_pulled, mut r2_pulled) = (false, false);
+
+        loop {
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => READ_TIMEOUT,
+            };
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            match self.update_timeout(remaining.min(READ_TIMEOUT)) {
+                Ok(0) | Err(Error::UnsupportedReport { .. }) => continue,
+                Ok(_) => {}
+                Err(err) => return Err(err),
+            }
+
+            let state = self.state();
+            let (l2, r2) = (state.l2().axis() > RELEASED, state.r2().axis() > RELEASED);
+            l2_pulled |= l2;
+            r2_pulled |= r2;
+
+            if l2 || r2 {
+                pulled.push(state);
+            } else if l2_pulled && r2_pulled {
+                return Ok(Some(TriggerCalibration::from_samples(&pulled)));
+            }
+        }
+    }
+
+    /// Set the maximum axis of the back triggers, used by the normalized back triggers.
+    ///
+    /// The calibration only affects [`l2_normalized`] and [`r2_normalized`]. By default, the back
+    /// triggers are assumed to reach the full axis.
+    ///
+    /// [`l2_normalized`]: fn@crate::DualSense::l2_normalized
+    /// [`r2_normalized`]: fn@crate::DualSense::r2_normalized
+    pub fn set_trigger_calibration(&self, calibration: TriggerCalibration) {
+        self.trigger_calibration.set(calibration);
+    }
+
+    /// Get the maximum axis of the back triggers.
+    pub fn trigger_calibration(&self) -> TriggerCalibration {
+        self.trigger_calibration.get()
+    }
+
+    /// Get the axis of the L2 trigger normalized from `0.0` to `1.0`, with the
+    /// [trigger calibration] applied.
+    ///
+    /// [trigger calibration]: fn@crate::DualSense::set_trigger_calibration
+    pub fn l2_normalized(&self) -> f32 {
+        self.trigger_calibration().l2_normalized(&self.state())
+    }
+
+    /// Get the axis of the R2 trigger normalized from `0.0` to `1.0`, with the
+    /// [trigger calibration] applied.
+    ///
+    /// [trigger calibration]: fn@crate::DualSense::set_trigger_calibration
+    pub fn r2_normalized(&self) -> f32 {
+        self.trigger_calibration().r2_normalized(&self.state())
+    }
+
+    /// Move the controller to a background thread that keeps updating it.
+    ///
+    /// The thread updates the controller as soon as new reports arrive, independently of the
+    /// current [`mode`], and publishes the latest state in the returned [`PollHandle`]. Reports
+    /// not supported by the crate are skipped, while any other error stops the thread.
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::DualSense;
+    ///
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    /// let poller = ds.spawn_poller();
+    ///
+    /// // Somewhere in the game loop.
+    /// if poller.latest().cross().is_pressed() {
+    ///     println!("Cross is being pressed!");
+    /// }
+    ///
+    /// // Get the controller back when done.
+    /// let ds = poller.stop().unwrap();
+    /// ```
+    ///
+    /// [`mode`]: fn@crate::DualSense::mode
+    /// [`PollHandle`]: struct@crate::poller::PollHandle
+    pub fn spawn_poller(self) -> PollHandle {
+        PollHandle::spawn(self)
+    }
+
+    /// Start recording the reports read by the controller to `writer`.
+    ///
+    /// The controller is moved to the returned [`Recorder`], which records every report read
+    /// while updating it. See the [`recording`] module for the format of the recording.
+    ///
+    /// [`Recorder`]: struct@crate::recording::Recorder
+    /// [`recording`]: mod@crate::recording
+    pub fn record<W: Write>(self, writer: W) -> Recorder<W> {
+        Recorder::new(self, writer)
     }
 
-    /// Get the current mode.
-    pub fn mode(&self) -> Mode {
-        self.mode.get()
+    /// Get the file descriptor of the controller, to use it in custom event loops.
+    ///
+    /// The descriptor becomes readable whenever the controller sends a new report, so it can be
+    /// registered in an `epoll` (or `mio`) loop, calling [`update`] when it is ready. The
+    /// descriptor is owned by the controller, so it must not be closed nor read directly.
+    ///
+    /// With the `linux-hidraw` feature, this is the descriptor the reports are read from. HIDAPI
+    /// does not expose its own descriptor, so when it uses its hidraw backend, the device node of
+    /// the controller is opened read-only alongside it instead. Since that descriptor is never
+    /// read, it stays readable after the first report, and should be registered in
+    /// edge-triggered mode (e.g. `EPOLLET`), which wakes up once per new report.
+    ///
+    /// This method returns `None` when there is no device node, such as with the libusb backend
+    /// of HIDAPI, a controller bound by its IDs instead of its path, or a mock device. The
+    /// descriptor may change after the controller reconnects, so it should be fetched again then.
+    ///
+    /// [`update`]: fn@crate::DualSense::update
+    #[cfg(target_os = "linux")]
+    pub fn as_raw_fd(&self) -> Option<std::os::fd::RawFd> {
+        self.controller.raw_fd()
     }
 
-    /// Get the current controller state.
+    /// Get the raw input report used to build the current controller state.
     ///
-    /// If [`update`] was not called at least once, the returned state is not representative of the
-    /// controller *actual* state. As such, this method should be called *only* after at least one
-    /// update from the controller.
+    /// This is the unparsed 64-byte USB report, as sent by the controller during the last
+    /// successful [`update`]. When the controller is connected through Bluetooth, its inputs are
+    /// moved to where they are in the USB report, so the layout is always the same. It is mostly
+    /// useful for debugging and for inspecting parts of the report that are not yet parsed by this
+    /// crate. If [`update`] was not called at least once, the report is filled with zeros.
     ///
     /// [`update`]: fn@crate::DualSense::update
-    pub fn state(&self) -> DualSenseState {
-        self.state.get()
+    pub fn raw_report(&self) -> [u8; 64] {
+        *self.report.get().as_array()
+    }
+
+    /// Get the wall-clock time between the last two states read from the controller.
+    ///
+    /// The controller sends a new report every 4ms (250hz) through USB, so an interval much
+    /// longer than that means the reports are not being read fast enough, or that some of them
+    /// were dropped. Only successful updates are measured, so updates that read nothing (e.g. in
+    /// _non-blocking_ mode) do not affect the interval.
+    ///
+    /// Until two states are read after binding, there is no interval, and `None` is returned.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// device.push_report(&report);
+    ///
+    /// ds.update().unwrap();
+    /// assert_eq!(ds.report_interval(), None);
+    /// ds.update().unwrap();
+    /// assert!(ds.report_interval().is_some());
+    /// # }
+    /// ```
+    pub fn report_interval(&self) -> Option<Duration> {
+        self.report_interval.get()
+    }
+}
+
+/// The shape of an input report that can be read from the controller.
+struct InputReport {
+    /// The report ID, which is the first byte of the report.
+    id: u8,
+    /// The length of the report, in bytes, including the ID.
+    len: usize,
+    /// Parse the report into a controller state, along with its inputs laid out as in the USB
+    /// report.
+    parse: fn(&[u8], KnownController) -> Result<ParsedReport>,
+}
+
+/// A parsed input report: the controller state and the inputs laid out as in the USB report.
+type ParsedReport = (DualSenseState, RawInputReportUSB);
+
+/// The input reports supported by [`DualSense::update`].
+///
+/// Supporting a new report only requires adding it here.
+const INPUT_REPORTS: [InputReport; 2] = [
+    // The USB input report.
+    InputReport {
+        id: 0x01,
+        len: 64,
+        parse: |bytes, kind| {
+            let report = RawInputReportUSB::from_slice(bytes).ok_or(unsupported(bytes))?;
+
+            Ok((report.parse(kind)?, report))
+        },
+    },
+    // The full Bluetooth input report.
+    InputReport {
+        id: 0x31,
+        len: 78,
+        parse: |bytes, kind| {
+            let report = RawInputReportBT::from_slice(bytes).ok_or(unsupported(bytes))?;
+
+            Ok((report.parse(kind)?, report.to_usb()))
+        },
+    },
+];
+
+/// The length of the longest report in [`INPUT_REPORTS`].
+const MAX_INPUT_REPORT_LEN: usize = 78;
+
+/// Build the error of an unsupported report made of `bytes`.
+fn unsupported(bytes: &[u8]) -> Error {
+    Error::UnsupportedReport {
+        id: bytes.first().copied().unwrap_or(0),
+        len: bytes.len(),
     }
 }
 
+/// The number of live binds.
+///
+/// HIDAPI is shared by every bind, so it is only finalized when the last one is dropped. A mutex
+/// is used instead of an atomic so that no bind is created while HIDAPI is being finalized: the
+/// lock is held from before the device is enumerated and opened until the count is incremented.
+static BINDS: Mutex<usize> = Mutex::new(0);
+
+/// The connection preference used when binding, set with [`DualSense::prefer`].
+static PREFERENCE: Mutex<ConnectionPreference> = Mutex::new(ConnectionPreference::Any);
+
 impl Drop for DualSense {
     fn drop(&mut self) {
-        // TODO: Unwrapping is not the best choice, maybe do something else?
-        hidapi::exit().unwrap();
+        // A poisoned lock still holds a valid count, since it is only changed by a single
+        // operation.
+        let mut binds = BINDS.lock().unwrap_or_else(PoisonError::into_inner);
+        *binds -= 1;
+        if *binds > 0 {
+            return;
+        }
+
+        // Panicking in a destructor can abort the whole process if it is already unwinding, and
+        // there is nothing the caller could do about a failed clean up anyway.
+        if let Err(err) = hidapi::exit() {
+            eprintln!("duplosentido: {}", Error::from(err));
+        }
     }
 }
 
@@ -214,25 +2478,183 @@ impl Drop for DualSense {
 /// This state has all the common buttons and readings from the controller. There is more
 /// information that could be read from the controller. But, in such cases, you should
 /// call...(TODO: continue with other reports maybe)
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bevy", derive(bevy_ecs::prelude::Resource))]
 pub struct DualSenseState {
     sticks: StickGroup,
     directional_pad: DPadDirection,
     action_buttons: ActionButtonGroup,
     menus: MenuGroup,
-    // TODO: Find a good and simple way to expose the TouchPad.
-    #[allow(unused)]
     touchpad: TouchPadState,
     front_triggers: FrontTriggerGroup,
     back_triggers: BackTriggerGroup,
     angular_velocity: AngularVelocityState,
     acceleration: AccelerationState,
+    sensor_timestamp: u32,
+    sequence: u8,
     plugged: PluggedGroup,
     temperature: TemperatureState,
     power: PowerGroup,
+    edge: Option<EdgeButtons>,
+    initialized: bool,
 }
 
 impl DualSenseState {
+    /// Try building a controller state from a raw USB input report.
+    ///
+    /// The first byte of `bytes` is the report ID, and it must be `0x01` (the USB input report),
+    /// otherwise [`Error::UnsupportedReport`] is returned. The layout of the remaining bytes is the
+    /// same one sent by the controller, see [`DualSense::raw_report`].
+    ///
+    /// This makes it possible to build states without a controller, which is useful for testing
+    /// the logic that consumes them:
+    ///
+    /// ```rust
+    /// use duplosentido::{mappings::Button, DualSenseState};
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01; // USB input report.
+    /// report[8] = 0x08; // Directional pad released.
+    /// let released = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// report[8] |= 0b0010_0000; // Cross pressed.
+    /// report[9] |= 0b0000_0010; // R1 pressed.
+    /// let pressed = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert!(pressed.cross().is_pressed());
+    /// assert_eq!(pressed.just_pressed(&released), vec![Button::Cross, Button::R1]);
+    /// assert_eq!(released.just_released(&pressed), vec![Button::Cross, Button::R1]);
+    ///
+    /// assert!(DualSenseState::from_raw([0_u8; 64]).is_err());
+    /// ```
+    ///
+    /// If a field of the report has a value that can't be sent by the controller (e.g. a
+    /// directional pad direction above `8`), [`Error::UnsupportedReport`] is also returned. Fields
+    /// reported by the controller but not known by this crate, such as [`PowerState::Unknown`],
+    /// are kept as they are instead:
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x09;
+    ///
+    /// assert!(DualSenseState::from_raw(report).is_err());
+    /// ```
+    ///
+    /// [`PowerState::Unknown`]: crate::mappings::PowerState::Unknown
+    /// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+    /// [`DualSense::raw_report`]: fn@crate::DualSense::raw_report
+    pub fn from_raw(bytes: [u8; 64]) -> Result<DualSenseState> {
+        DualSenseState::from_raw_for(bytes, KnownController::DualSense)
+    }
+
+    /// Try building a controller state from a raw USB input report sent by the given model.
+    ///
+    /// This method behaves exactly like [`from_raw`], but it also parses the inputs that only
+    /// exist in the given model, such as the extra buttons of the DualSense Edge:
+    ///
+    /// ```rust
+    /// use duplosentido::{DualSenseState, KnownController};
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[10] = 0b0101_0000; // FnL and left paddle pressed.
+    ///
+    /// let standard = DualSenseState::from_raw_for(report, KnownController::DualSense).unwrap();
+    /// assert!(standard.edge().is_none());
+    ///
+    /// let edge = DualSenseState::from_raw_for(report, KnownController::DualSenseEdge).unwrap();
+    /// let buttons = edge.edge().unwrap();
+    /// assert!(buttons.left_function().is_pressed());
+    /// assert!(buttons.left_paddle().is_pressed());
+    /// assert!(!buttons.right_function().is_pressed());
+    /// assert!(!buttons.right_paddle().is_pressed());
+    /// ```
+    ///
+    /// [`from_raw`]: fn@Self::from_raw
+    pub fn from_raw_for(bytes: [u8; 64], kind: KnownController) -> Result<DualSenseState> {
+        match bytes[0] {
+            0x01 => RawInputReportUSB::new(bytes).parse(kind),
+            _ => Err(unsupported(&bytes)),
+        }
+    }
+
+    /// Get the extra buttons of the DualSense Edge.
+    ///
+    /// This is `None` when the state was not sent by a DualSense Edge. See
+    /// [`from_raw_for`] for an example.
+    ///
+    /// [`from_raw_for`]: fn@Self::from_raw_for
+    pub fn edge(&self) -> Option<EdgeButtons> {
+        self.edge
+    }
+
+    /// Build the state of a controller that was not updated yet.
+    ///
+    /// Every input is at rest (e.g. the analog sticks are centered), but the state is marked as
+    /// uninitialized.
+    pub(crate) fn uninitialized() -> DualSenseState {
+        let mut report = [0_u8; 64];
+        report[1..=4].fill(STICK_CENTER);
+        report[8] = 0x08;
+
+        parse_report(&report, 0).expect("The directional pad is released")
+    }
+
+    /// Return `true` if the state was parsed from an input report and `false` otherwise.
+    ///
+    /// States read from the controller, or built from a report with [`from_raw`], are initialized.
+    /// States built without a report, such as the state of a controller that was never updated or
+    /// the ones built by [`from_button_bits`], are uninitialized. Their inputs are at rest (other
+    /// than the given buttons), but they do not reflect the controller *actual* state, so they
+    /// should not be acted upon:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, mappings::DPadDirection, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// let state = ds.state();
+    /// assert!(!state.is_initialized());
+    /// assert_eq!(state.dpad(), DPadDirection::None);
+    /// assert_eq!((state.left_stick().x(), state.left_stick().y()), (128, 128));
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    /// ds.update().unwrap();
+    ///
+    /// assert!(ds.state().is_initialized());
+    /// # }
+    /// ```
+    ///
+    /// [`from_raw`]: fn@Self::from_raw
+    /// [`from_button_bits`]: fn@Self::from_button_bits
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    /// Return `true` if both states have the same inputs, ignoring the motion sensors and the
+    /// timestamps.
+    fn same_inputs(&self, other: &DualSenseState) -> bool {
+        let mut other = *other;
+        other.angular_velocity = self.angular_velocity;
+        other.acceleration = self.acceleration;
+        other.sensor_timestamp = self.sensor_timestamp;
+        other.sequence = self.sequence;
+        other.touchpad.timestamp = self.touchpad.timestamp;
+
+        *self == other
+    }
+
     /// Get the left analog stick state.
     pub fn left_stick(&self) -> StickState {
         self.sticks.left
@@ -308,6 +2730,25 @@ impl DualSenseState {
         self.back_triggers.r2
     }
 
+    /// Get the `L3` (left analog stick) button state.
+    ///
+    /// This is the same as `left_stick().button()`.
+    pub fn l3(&self) -> ButtonState {
+        self.sticks.left.button()
+    }
+
+    /// Get the `R3` (right analog stick) button state.
+    ///
+    /// This is the same as `right_stick().button()`.
+    pub fn r3(&self) -> ButtonState {
+        self.sticks.right.button()
+    }
+
+    /// Get the touchpad state.
+    pub fn touchpad(&self) -> TouchPadState {
+        self.touchpad
+    }
+
     /// Get the angular velocity of the controller.
     pub fn gyroscope(&self) -> AngularVelocityState {
         self.angular_velocity
@@ -318,6 +2759,75 @@ impl DualSenseState {
         self.acceleration
     }
 
+    /// Get the timestamp of the motion sensor readings.
+    ///
+    /// The timestamp is a counter that increments in units of approximately 0.33 microseconds
+    /// and wraps around when it overflows. Its absolute value is meaningless, but the difference
+    /// between two readings can be used to integrate the [`gyroscope`] readings. See
+    /// [`sensor_delta`] for an easier way of computing it.
+    ///
+    /// [`gyroscope`]: fn@Self::gyroscope
+    /// [`sensor_delta`]: fn@Self::sensor_delta
+    pub fn sensor_timestamp(&self) -> u32 {
+        self.sensor_timestamp
+    }
+
+    /// Get the time elapsed between the motion sensor readings of `previous` and this state.
+    ///
+    /// The computation handles the [`sensor_timestamp`] wrapping around, so `previous` should be
+    /// a state read *before* this one.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    /// use std::time::Duration;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[28..=31].copy_from_slice(&u32::MAX.to_le_bytes());
+    /// let previous = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// // 4ms (one report at 250hz) later, with the counter wrapping around.
+    /// report[28..=31].copy_from_slice(&11_999_u32.to_le_bytes());
+    /// let current = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(current.sensor_delta(&previous), Duration::from_millis(4));
+    /// ```
+    ///
+    /// [`sensor_timestamp`]: fn@Self::sensor_timestamp
+    pub fn sensor_delta(&self, previous: &DualSenseState) -> Duration {
+        let ticks = self
+            .sensor_timestamp
+            .wrapping_sub(previous.sensor_timestamp);
+
+        Duration::from_nanos(ticks as u64 * 1000 / 3)
+    }
+
+    /// Get the sequence number of the report the state was read from.
+    ///
+    /// The controller increments the sequence number for every report it sends, wrapping around
+    /// from `255` back to `0`. As such, a gap between the sequence numbers of two consecutive
+    /// states reveals lost reports (e.g. because of a flaky Bluetooth connection, or a consumer
+    /// that is too slow):
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[7] = 254;
+    /// let previous = DualSenseState::from_raw(report).unwrap();
+    /// report[7] = 1;
+    /// let current = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// let lost = current.sequence().wrapping_sub(previous.sequence()) - 1;
+    /// assert_eq!(lost, 2);
+    /// ```
+    pub fn sequence(&self) -> u8 {
+        self.sequence
+    }
+
     /// Get the state of the headphone.
     pub fn headphone(&self) -> PluggedState {
         self.plugged.headphone
@@ -333,6 +2843,17 @@ impl DualSenseState {
         self.plugged.usb
     }
 
+    /// Return `true` if the haptic low-pass filter flag is set and `false` otherwise.
+    ///
+    /// The meaning of this flag is still not fully understood. It is reported right after the
+    /// plugged states and seems to be related to the haptic feedback (it may indicate that the
+    /// controller is filtering the high frequencies of its haptic actuators), so it is exposed to
+    /// help correlating it with the output reports. Its semantics may change once it is better
+    /// understood.
+    pub fn haptic_low_pass_filter(&self) -> bool {
+        self.plugged.haptic_low_pass_filter.is_plugged()
+    }
+
     /// Get the temperature of the controller.
     pub fn temperature(&self) -> TemperatureState {
         self.temperature
@@ -343,259 +2864,809 @@ impl DualSenseState {
         self.power.state
     }
 
-    /// Get the percentage of the controller battery.
+    /// Get the percentage of the controller battery, from `0` to `100`.
+    ///
+    /// The controller only reports its battery level in steps of 10%, so the returned value is
+    /// always a multiple of `10` and has a granularity of ±10%. See [`battery_level_raw`] for the
+    /// value reported by the controller.
+    ///
+    /// [`battery_level_raw`]: fn@Self::battery_level_raw
     pub fn battery_percent(&self) -> u8 {
-        self.power.percent
+        (self.power.level * 10).min(100)
     }
-}
 
-impl From<RawInputReportUSB> for DualSenseState {
-    fn from(value: RawInputReportUSB) -> Self {
-        let value = value.as_array();
+    /// Get the raw battery level of the controller, as reported by it.
+    ///
+    /// The level usually ranges from `0` to `10`, each step representing 10% of the battery.
+    pub fn battery_level_raw(&self) -> u8 {
+        self.power.level
+    }
 
-        assert!(
-            value[0] == 1 || value[0] == 0,
-            "Report must be either of type 1 or empty"
-        );
+    /// Get an iterator over the buttons currently pressed.
+    ///
+    /// The buttons are yielded in the same order as [`Button::ALL`].
+    ///
+    /// [`Button::ALL`]: crate::mappings::Button::ALL
+    pub fn pressed_buttons(&self) -> impl Iterator<Item = Button> + '_ {
+        Button::ALL
+            .into_iter()
+            .filter(|&b| self.button(b).is_pressed())
+    }
 
-        let mask_shift = |byte: u8, mask: u8| (byte & mask) >> mask.trailing_zeros();
+    /// Get every digital button packed in a single bitfield.
+    ///
+    /// Each pressed button sets its bit, as defined in [`bits`]. This makes it cheap to store or
+    /// send the buttons, and to compare two states, since the changed buttons are a single XOR
+    /// away:
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::bits;
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let previous = DualSenseState::from_button_bits(bits::CROSS | bits::L1);
+    /// let current = DualSenseState::from_button_bits(bits::CROSS | bits::DPAD_UP);
+    ///
+    /// let changed = current.button_bits() ^ previous.button_bits();
+    /// assert_eq!(changed, bits::L1 | bits::DPAD_UP);
+    /// ```
+    ///
+    /// [`bits`]: mod@crate::mappings::bits
+    pub fn button_bits(&self) -> u32 {
+        self.pressed_buttons()
+            .fold(0, |bits, button| bits | button.bit())
+    }
+
+    /// Build a controller state with the buttons packed in `bits` pressed.
+    ///
+    /// This is the inverse of [`button_bits`]. Every other input is left in its rest position
+    /// (e.g. the analog sticks are centered). Since the directional pad can only point in one
+    /// direction, opposite arrows pressed at the same time cancel each other out. Reserved bits
+    /// are ignored. The state was not parsed from a report, so it is uninitialized (see
+    /// [`is_initialized`]).
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::{bits, Button, DPadDirection};
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let state = DualSenseState::from_button_bits(bits::DPAD_UP | bits::DPAD_LEFT | bits::R2);
+    ///
+    /// assert_eq!(state.dpad(), DPadDirection::NorthWest);
+    /// assert!(state.button(Button::R2).is_pressed());
+    /// assert_eq!(state.button_bits(), bits::DPAD_UP | bits::DPAD_LEFT | bits::R2);
+    /// assert!(!state.is_initialized());
+    /// ```
+    ///
+    /// [`button_bits`]: fn@Self::button_bits
+    /// [`is_initialized`]: fn@Self::is_initialized
+    pub fn from_button_bits(bits: u32) -> DualSenseState {
+        use crate::mappings::bits::*;
+
+        let pressed = |bit: u32| bits & bit != 0;
+        let up = pressed(DPAD_UP) && !pressed(DPAD_DOWN);
+        let down = pressed(DPAD_DOWN) && !pressed(DPAD_UP);
+        let right = pressed(DPAD_RIGHT) && !pressed(DPAD_LEFT);
+        let left = pressed(DPAD_LEFT) && !pressed(DPAD_RIGHT);
+        let hat = match (up, right, down, left) {
+            (true, false, _, false) => 0,
+            (true, true, _, _) => 1,
+            (false, true, false, _) => 2,
+            (_, true, true, _) => 3,
+            (false, false, true, false) => 4,
+            (_, _, true, true) => 5,
+            (false, false, false, true) => 6,
+            (true, _, _, true) => 7,
+            _ => 8,
+        };
 
-        let sticks = {
-            const L3_MASK: u8 = 0b0100_0000;
-            const R3_MASK: u8 = 0b1000_0000;
+        let mut report = [0_u8; 64];
+        report[1..=4].fill(STICK_CENTER);
+        report[8] = hat;
+
+        let buttons = [
+            (SQUARE, 8, 0b0001_0000),
+            (CROSS, 8, 0b0010_0000),
+            (CIRCLE, 8, 0b0100_0000),
+            (TRIANGLE, 8, 0b1000_0000),
+            (L1, 9, 0b0000_0001),
+            (R1, 9, 0b0000_0010),
+            (L2, 9, 0b0000_0100),
+            (R2, 9, 0b0000_1000),
+            (CREATE, 9, 0b0001_0000),
+            (OPTIONS, 9, 0b0010_0000),
+            (L3, 9, 0b0100_0000),
+            (R3, 9, 0b1000_0000),
+            (HOME, 10, 0b0000_0001),
+            (TOUCHPAD, 10, 0b0000_0010),
+            (MUTE, 10, 0b0000_0100),
+        ];
+        for (bit, byte, mask) in buttons {
+            if pressed(bit) {
+                report[byte] |= mask;
+            }
+        }
 
-            let state = ButtonState::from(mask_shift(value[9], L3_MASK));
-            let position = StickCoordinates {
-                x: value[1],
-                y: value[2],
-            };
-            let left = StickState { state, position };
+        parse_report(&report, 0).expect("The directional pad has a valid direction")
+    }
 
-            let state = ButtonState::from(mask_shift(value[9], R3_MASK));
-            let position = StickCoordinates {
-                x: value[3],
-                y: value[4],
-            };
-            let right = StickState { state, position };
+    /// Return `true` if any digital button is pressed and `false` otherwise.
+    ///
+    /// Every button in [`Button::ALL`] is considered.
+    ///
+    /// [`Button::ALL`]: crate::mappings::Button::ALL
+    pub fn any_button_pressed(&self) -> bool {
+        self.pressed_buttons().next().is_some()
+    }
 
-            StickGroup { left, right }
-        };
+    /// Return `true` if the user is giving any input and `false` otherwise.
+    ///
+    /// Besides the digital buttons (see [`any_button_pressed`]), an analog stick deflected further
+    /// than `stick_threshold` from its center, or a back trigger pulled further than
+    /// `stick_threshold`, also counts as input. Both are measured in the normalized `0.0` to `1.0`
+    /// range. Motion data is ignored, since the controller is never perfectly still. This is useful
+    /// to detect an idle user:
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[1..=4].copy_from_slice(&[130, 126, 128, 128]);
+    /// report[8] = 0x08;
+    /// report[16..28].fill(0x7F); // The controller is moving.
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert!(!state.any_button_pressed());
+    /// assert!(!state.any_input(0.1));
+    ///
+    /// report[5] = 200; // L2 pulled.
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    /// assert!(state.any_input(0.1));
+    /// ```
+    ///
+    /// [`any_button_pressed`]: fn@Self::any_button_pressed
+    pub fn any_input(&self, stick_threshold: f32) -> bool {
+        let stick =
+            |stick: StickState| stick.x_normalized().hypot(stick.y_normalized()) > stick_threshold;
+        let trigger = |trigger: BackTriggerState| trigger.axis() as f32 / 255.0 > stick_threshold;
+
+        self.any_button_pressed()
+            || stick(self.sticks.left)
+            || stick(self.sticks.right)
+            || trigger(self.back_triggers.l2)
+            || trigger(self.back_triggers.r2)
+    }
+
+    /// Get the buttons that are pressed now but were released in the `previous` state.
+    ///
+    /// This is useful to react only once to a button press (its rising edge), instead of every
+    /// time the state is updated while the button is held. The buttons are returned in the same
+    /// order as [`Button::ALL`].
+    ///
+    /// ```rust,no_run
+    /// use duplosentido::{mappings::Button, DualSense};
+    ///
+    /// let ds = DualSense::bind().expect("At least one controller should be connected");
+    /// let mut previous = ds.state();
+    ///
+    /// loop {
+    ///     ds.update().unwrap();
+    ///     let current = ds.state();
+    ///
+    ///     if current.just_pressed(&previous).contains(&Button::Cross) {
+    ///         println!("Jump!");
+    ///     }
+    ///
+    ///     previous = current;
+    /// }
+    /// ```
+    ///
+    /// [`Button::ALL`]: crate::mappings::Button::ALL
+    pub fn just_pressed(&self, previous: &DualSenseState) -> Vec<Button> {
+        Button::ALL
+            .into_iter()
+            .filter(|&b| self.button(b).is_pressed() && !previous.button(b).is_pressed())
+            .collect()
+    }
+
+    /// Get the buttons that are released now but were pressed in the `previous` state.
+    ///
+    /// This is the counterpart of [`just_pressed`], returning the falling edges instead. The
+    /// buttons are returned in the same order as [`Button::ALL`].
+    ///
+    /// [`just_pressed`]: fn@Self::just_pressed
+    /// [`Button::ALL`]: crate::mappings::Button::ALL
+    pub fn just_released(&self, previous: &DualSenseState) -> Vec<Button> {
+        Button::ALL
+            .into_iter()
+            .filter(|&b| !self.button(b).is_pressed() && previous.button(b).is_pressed())
+            .collect()
+    }
 
-        let directional_pad = {
-            const DPAD_MASK: u8 = 0b0000_1111;
+    /// Get the components that changed between the `previous` state and this one.
+    ///
+    /// This is richer than comparing the states, as it tells *what* changed, which is useful for
+    /// logging and debugging input pipelines. The analog inputs that are always slightly moving
+    /// are compared with a tolerance, so their noise does not flood the diff:
+    /// - The analog sticks must move more than `2` units in any axis (out of `255`).
+    /// - The gyroscope must change more than `16` units (1 degree per second) in any axis.
+    /// - The accelerometer must change more than `82` units (0.01 g) in any axis.
+    ///
+    /// The timestamps (and the sequence number) always change, so they are not compared.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::{bits, Button, DPadDirection};
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let previous = DualSenseState::from_button_bits(bits::CROSS);
+    /// let current = DualSenseState::from_button_bits(bits::CIRCLE | bits::DPAD_UP);
+    ///
+    /// let diff = current.diff(&previous);
+    /// assert_eq!(diff.pressed, vec![Button::DPadUp, Button::Circle]);
+    /// assert_eq!(diff.released, vec![Button::Cross]);
+    /// assert_eq!(diff.dpad, Some(DPadDirection::North));
+    /// assert!(!diff.left_stick && !diff.acceleration);
+    ///
+    /// assert!(current.diff(&current).is_empty());
+    /// ```
+    pub fn diff(&self, previous: &DualSenseState) -> StateDiff {
+        const STICK_EPSILON: u8 = 2;
+        const GYROSCOPE_EPSILON: u16 = 16;
+        const ACCELEROMETER_EPSILON: u16 = 82;
+
+        let stick = |a: StickState, b: StickState| {
+            a.x().abs_diff(b.x()) > STICK_EPSILON
+                || a.y().abs_diff(b.y()) > STICK_EPSILON
+                || a.button() != b.button()
+        };
+        let sensor = |a: [i16; 3], b: [i16; 3], epsilon: u16| {
+            a.iter().zip(b).any(|(a, b)| a.abs_diff(b) > epsilon)
+        };
+        let dpad = self.dpad();
+
+        StateDiff {
+            pressed: self.just_pressed(previous),
+            released: self.just_released(previous),
+            dpad: (dpad != previous.dpad()).then_some(dpad),
+            left_stick: stick(self.left_stick(), previous.left_stick()),
+            right_stick: stick(self.right_stick(), previous.right_stick()),
+            l2: self.l2().axis() != previous.l2().axis(),
+            r2: self.r2().axis() != previous.r2().axis(),
+            touchpad: self.touchpad().fingers() != previous.touchpad().fingers(),
+            gyroscope: sensor(
+                self.gyroscope().as_array(),
+                previous.gyroscope().as_array(),
+                GYROSCOPE_EPSILON,
+            ),
+            acceleration: sensor(
+                self.acceleration().as_array(),
+                previous.acceleration().as_array(),
+                ACCELEROMETER_EPSILON,
+            ),
+        }
+    }
 
-            DPadDirection::from(mask_shift(value[8], DPAD_MASK))
+    /// Get the state of any digital button.
+    ///
+    /// This is equivalent to calling the dedicated getter of the button (e.g. [`square`] for
+    /// [`Button::Square`]), but makes it possible to query buttons chosen at runtime, like in
+    /// remapping interfaces.
+    ///
+    /// [`square`]: fn@Self::square
+    /// [`Button::Square`]: crate::mappings::Button::Square
+    pub fn button(&self, button: Button) -> ButtonState {
+        let dpad = |pressed: bool| {
+            if pressed {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            }
         };
 
-        let action_buttons = {
-            const SQUARE_MASK: u8 = 0b0001_0000;
-            const CROSS_MASK: u8 = 0b0010_0000;
-            const CIRCLE_MASK: u8 = 0b0100_0000;
-            const TRIANGLE_MASK: u8 = 0b1000_0000;
+        match button {
+            Button::DPadUp => dpad(self.directional_pad.up()),
+            Button::DPadRight => dpad(self.directional_pad.right()),
+            Button::DPadDown => dpad(self.directional_pad.down()),
+            Button::DPadLeft => dpad(self.directional_pad.left()),
+            Button::Square => self.action_buttons.square,
+            Button::Cross => self.action_buttons.cross,
+            Button::Circle => self.action_buttons.circle,
+            Button::Triangle => self.action_buttons.triangle,
+            Button::L1 => self.front_triggers.l1,
+            Button::R1 => self.front_triggers.r1,
+            Button::L2 => self.back_triggers.l2.button(),
+            Button::R2 => self.back_triggers.r2.button(),
+            Button::L3 => self.l3(),
+            Button::R3 => self.r3(),
+            Button::Create => self.menus.create,
+            Button::Options => self.menus.options,
+            Button::Home => self.menus.home,
+            Button::Mute => self.menus.mute,
+            Button::TouchPad => self.touchpad.state,
+        }
+    }
+}
 
-            let byte = value[8];
+/// A compact, single line summary of the state, useful for logging.
+///
+/// The summary contains the pressed buttons (or `idle` if none is pressed), the position of the
+/// analog sticks, the axes of the back triggers, the direction of the directional pad and the
+/// battery percentage:
+///
+/// ```rust
+/// use duplosentido::DualSenseState;
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[1..=4].copy_from_slice(&[128, 128, 200, 30]);
+/// report[8] = 0x08;
+/// report[53] = 0x05;
+/// let state = DualSenseState::from_raw(report).unwrap();
+///
+/// assert_eq!(
+///     state.to_string(),
+///     "buttons: idle | left: (128, 128) | right: (200, 30) | l2: 0 | r2: 0 | dpad: None | battery: 50%"
+/// );
+/// ```
+impl fmt::Display for DualSenseState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buttons:")?;
+        let mut pressed = self.pressed_buttons().peekable();
+        if pressed.peek().is_none() {
+            write!(f, " idle")?;
+        }
+        for button in pressed {
+            write!(f, " {button:?}")?;
+        }
 
-            let square = ButtonState::from(mask_shift(byte, SQUARE_MASK));
-            let cross = ButtonState::from(mask_shift(byte, CROSS_MASK));
-            let circle = ButtonState::from(mask_shift(byte, CIRCLE_MASK));
-            let triangle = ButtonState::from(mask_shift(byte, TRIANGLE_MASK));
+        let (left, right) = (self.left_stick(), self.right_stick());
+        write!(
+            f,
+            " | left: ({}, {}) | right: ({}, {}) | l2: {} | r2: {} | dpad: {:?} | battery: {}%",
+            left.x(),
+            left.y(),
+            right.x(),
+            right.y(),
+            self.l2().axis(),
+            self.r2().axis(),
+            self.dpad(),
+            self.battery_percent()
+        )
+    }
+}
 
-            ActionButtonGroup {
-                square,
-                cross,
-                circle,
-                triangle,
-            }
+/// Only the digital buttons (see [`button_bits`]) are hashed, since the analog readings (e.g. the
+/// motion sensors) change on every report, making them a poor key. This is still consistent with
+/// [`PartialEq`], as equal states always have the same buttons pressed:
+///
+/// ```rust
+/// use duplosentido::mappings::bits;
+/// use duplosentido::DualSenseState;
+/// use std::collections::HashSet;
+///
+/// let seen: HashSet<_> = [bits::CROSS, bits::CROSS | bits::L1, bits::CROSS]
+///     .into_iter()
+///     .map(DualSenseState::from_button_bits)
+///     .collect();
+///
+/// assert_eq!(seen.len(), 2);
+/// ```
+///
+/// [`button_bits`]: fn@crate::DualSenseState::button_bits
+impl Hash for DualSenseState {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.button_bits().hash(state);
+    }
+}
+
+/// The components that changed between two controller states.
+///
+/// See [`DualSenseState::diff`] for how the states are compared.
+///
+/// [`DualSenseState::diff`]: fn@crate::DualSenseState::diff
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiff {
+    /// The buttons that were pressed, in the same order as [`Button::ALL`].
+    ///
+    /// [`Button::ALL`]: crate::mappings::Button::ALL
+    pub pressed: Vec<Button>,
+    /// The buttons that were released, in the same order as [`Button::ALL`].
+    ///
+    /// [`Button::ALL`]: crate::mappings::Button::ALL
+    pub released: Vec<Button>,
+    /// The new direction of the directional pad, if it changed.
+    pub dpad: Option<DPadDirection>,
+    /// If the left analog stick moved or was clicked.
+    pub left_stick: bool,
+    /// If the right analog stick moved or was clicked.
+    pub right_stick: bool,
+    /// If the axis of the left back trigger changed.
+    pub l2: bool,
+    /// If the axis of the right back trigger changed.
+    pub r2: bool,
+    /// If any finger touched, moved on or left the touchpad.
+    pub touchpad: bool,
+    /// If the angular velocity changed.
+    pub gyroscope: bool,
+    /// If the acceleration changed.
+    pub acceleration: bool,
+}
+
+impl StateDiff {
+    /// Return `true` if nothing changed and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        *self == StateDiff::default()
+    }
+}
+
+impl RawInputReportUSB {
+    /// Parse the report sent by a `kind` controller into a controller state.
+    fn parse(&self, kind: KnownController) -> Result<DualSenseState> {
+        parse_report_for(self.as_array(), 0, kind)
+    }
+}
+
+impl RawInputReportBT {
+    /// Parse the report sent by a `kind` controller into a controller state.
+    ///
+    /// The report ID is followed by a sequence number, so the inputs are one byte later than in
+    /// the USB report.
+    fn parse(&self, kind: KnownController) -> Result<DualSenseState> {
+        parse_report_for(self.as_array(), 1, kind)
+    }
+}
+
+/// Parse an input report sent by a `kind` controller into a controller state.
+///
+/// This behaves exactly like [`parse_report`], but it also parses the inputs that only exist in
+/// the given model, such as the extra buttons of the DualSense Edge.
+fn parse_report_for(bytes: &[u8], offset: usize, kind: KnownController) -> Result<DualSenseState> {
+    let mut state = parse_report(bytes, offset)?;
+
+    if kind == KnownController::DualSenseEdge {
+        const LEFT_FUNCTION_MASK: u8 = 0b0001_0000;
+        const RIGHT_FUNCTION_MASK: u8 = 0b0010_0000;
+        const LEFT_PADDLE_MASK: u8 = 0b0100_0000;
+        const RIGHT_PADDLE_MASK: u8 = 0b1000_0000;
+
+        let mask_shift = |byte: u8, mask: u8| (byte & mask) >> mask.trailing_zeros();
+        // The report is long enough, since it was already parsed above.
+        let byte = bytes[offset + 10];
+
+        state.edge = Some(EdgeButtons {
+            left_function: ButtonState::from(mask_shift(byte, LEFT_FUNCTION_MASK)),
+            right_function: ButtonState::from(mask_shift(byte, RIGHT_FUNCTION_MASK)),
+            left_paddle: ButtonState::from(mask_shift(byte, LEFT_PADDLE_MASK)),
+            right_paddle: ButtonState::from(mask_shift(byte, RIGHT_PADDLE_MASK)),
+        });
+    }
+
+    Ok(state)
+}
+
+/// Parse an input report into a controller state.
+///
+/// The inputs must be laid out as in the USB report, but they may start `offset` bytes later
+/// (e.g. the Bluetooth report has an extra byte after its ID), so every report can share the same
+/// parser. The report ID is *not* checked, so it must be checked by the caller. An ID of `0` is
+/// used by the states that were not read from a report, which are marked as uninitialized.
+///
+/// # Errors
+/// If the report is too short to hold every input after the `offset`, or if the directional pad
+/// has an invalid direction, [`Error::UnsupportedReport`] is returned.
+///
+/// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+fn parse_report(bytes: &[u8], offset: usize) -> Result<DualSenseState> {
+    if bytes.len() < offset + 64 {
+        return Err(unsupported(bytes));
+    }
+
+    // Skip the extra bytes, so every input is at the same index as in the USB report.
+    let value = &bytes[offset..];
+
+    let mask_shift = |byte: u8, mask: u8| (byte & mask) >> mask.trailing_zeros();
+
+    let sticks = {
+        const L3_MASK: u8 = 0b0100_0000;
+        const R3_MASK: u8 = 0b1000_0000;
+
+        let state = ButtonState::from(mask_shift(value[9], L3_MASK));
+        let position = StickCoordinates {
+            x: value[1],
+            y: value[2],
         };
+        let left = StickState { state, position };
 
-        let menus = {
-            const CREATE_MASK: u8 = 0b0001_0000;
-            const OPTIONS_MASK: u8 = 0b0010_0000;
-            const HOME_MASK: u8 = 0b0000_0001;
-            const MUTE_MASK: u8 = 0b0000_0100;
-
-            let create = ButtonState::from(mask_shift(value[9], CREATE_MASK));
-            let options = ButtonState::from(mask_shift(value[9], OPTIONS_MASK));
-            let home = ButtonState::from(mask_shift(value[10], HOME_MASK));
-            let mute = ButtonState::from(mask_shift(value[10], MUTE_MASK));
-
-            MenuGroup {
-                create,
-                options,
-                home,
-                mute,
-            }
+        let state = ButtonState::from(mask_shift(value[9], R3_MASK));
+        let position = StickCoordinates {
+            x: value[3],
+            y: value[4],
         };
+        let right = StickState { state, position };
 
-        let touchpad = {
-            const TOUCHPAD_MASK: u8 = 0b0000_0010;
-
-            let state = ButtonState::from(mask_shift(value[10], TOUCHPAD_MASK));
-            let finger = {
-                const INDEX_MASK: u8 = 0b0111_1111;
-                const TOUCHING_MASK: u8 = 0b1000_0000;
-                const X_MASK: u8 = 0b0000_1111;
-                const Y_MASK: u8 = 0b1111_0000;
-
-                let index = value[33] & INDEX_MASK;
-                let touching = mask_shift(value[33], TOUCHING_MASK) == 0;
-                let x = u16::from_ne_bytes([value[34], value[35] & X_MASK]);
-                let y = u16::from_ne_bytes([value[35] & Y_MASK, value[36]]);
-                let one = FingerData {
-                    index,
-                    is_touching: touching,
-                    x,
-                    y,
-                };
-
-                let index = value[37] & INDEX_MASK;
-                let touching = mask_shift(value[37], TOUCHING_MASK) == 0;
-                let x = u16::from_ne_bytes([value[38], value[39] & X_MASK]);
-                let y = u16::from_ne_bytes([value[39] & Y_MASK, value[40]]);
-                let two = FingerData {
-                    index,
-                    is_touching: touching,
-                    x,
-                    y,
-                };
-
-                [one, two]
-            };
-            let timestamp = value[41];
+        StickGroup { left, right }
+    };
 
-            TouchPadState {
-                state,
-                finger,
-                timestamp,
-            }
-        };
+    let directional_pad = {
+        const DPAD_MASK: u8 = 0b0000_1111;
 
-        let front_triggers = {
-            const L1_MASK: u8 = 0b0000_0001;
-            const R1_MASK: u8 = 0b0000_0010;
+        DPadDirection::try_from(mask_shift(value[8], DPAD_MASK)).map_err(|_| unsupported(bytes))?
+    };
 
-            let byte = value[9];
+    let action_buttons = {
+        const SQUARE_MASK: u8 = 0b0001_0000;
+        const CROSS_MASK: u8 = 0b0010_0000;
+        const CIRCLE_MASK: u8 = 0b0100_0000;
+        const TRIANGLE_MASK: u8 = 0b1000_0000;
 
-            let l1 = ButtonState::from(mask_shift(byte, L1_MASK));
-            let r1 = ButtonState::from(mask_shift(byte, R1_MASK));
+        let byte = value[8];
 
-            FrontTriggerGroup { l1, r1 }
-        };
+        let square = ButtonState::from(mask_shift(byte, SQUARE_MASK));
+        let cross = ButtonState::from(mask_shift(byte, CROSS_MASK));
+        let circle = ButtonState::from(mask_shift(byte, CIRCLE_MASK));
+        let triangle = ButtonState::from(mask_shift(byte, TRIANGLE_MASK));
 
-        let back_triggers = {
-            const L2_MASK: u8 = 0b0000_0100;
-            const L2_EFFECT_MASK: u8 = 0b1111_0000;
-            const L2_STATUS_MASK: u8 = 0b1111_0000;
-            const L2_STOP_MASK: u8 = 0b1111_0000;
-            const R2_MASK: u8 = 0b0000_1000;
-            const R2_EFFECT_MASK: u8 = 0b0000_1111;
-            const R2_STATUS_MASK: u8 = 0b1111_0000;
-            const R2_STOP_MASK: u8 = 0b1111_0000;
-
-            let state = ButtonState::from(mask_shift(value[9], L2_MASK));
-            let axis = Axis::new(value[5]);
-            let effect = BackTriggerEffect::from(mask_shift(value[48], L2_EFFECT_MASK));
-            let status = BackTriggerStatus::from((mask_shift(value[43], L2_STATUS_MASK), effect));
-            let stop = BackTriggerStop(mask_shift(value[43], L2_STOP_MASK));
-            let l2 = BackTriggerState {
-                state,
-                axis,
-                effect,
-                status,
-                stop,
+        ActionButtonGroup {
+            square,
+            cross,
+            circle,
+            triangle,
+        }
+    };
+
+    let menus = {
+        const CREATE_MASK: u8 = 0b0001_0000;
+        const OPTIONS_MASK: u8 = 0b0010_0000;
+        const HOME_MASK: u8 = 0b0000_0001;
+        const MUTE_MASK: u8 = 0b0000_0100;
+
+        let create = ButtonState::from(mask_shift(value[9], CREATE_MASK));
+        let options = ButtonState::from(mask_shift(value[9], OPTIONS_MASK));
+        let home = ButtonState::from(mask_shift(value[10], HOME_MASK));
+        let mute = ButtonState::from(mask_shift(value[10], MUTE_MASK));
+
+        MenuGroup {
+            create,
+            options,
+            home,
+            mute,
+        }
+    };
+
+    let touchpad = {
+        const TOUCHPAD_MASK: u8 = 0b0000_0010;
+
+        let state = ButtonState::from(mask_shift(value[10], TOUCHPAD_MASK));
+        let finger = {
+            const INDEX_MASK: u8 = 0b0111_1111;
+            const TOUCHING_MASK: u8 = 0b1000_0000;
+            const X_MASK: u8 = 0b0000_1111;
+            const Y_MASK: u8 = 0b1111_0000;
+
+            let index = value[33] & INDEX_MASK;
+            let touching = mask_shift(value[33], TOUCHING_MASK) == 0;
+            let x = u16::from_le_bytes([value[34], value[35] & X_MASK]);
+            let y = (mask_shift(value[35], Y_MASK) as u16) | (value[36] as u16) << 4;
+            let one = FingerData {
+                index,
+                is_touching: touching,
+                x,
+                y,
             };
 
-            let state = ButtonState::from(mask_shift(value[9], R2_MASK));
-            let axis = Axis::new(value[6]);
-            let effect = BackTriggerEffect::from(mask_shift(value[48], R2_EFFECT_MASK));
-            let status = BackTriggerStatus::from((mask_shift(value[42], R2_STATUS_MASK), effect));
-            let stop = BackTriggerStop(mask_shift(value[42], R2_STOP_MASK));
-            let r2 = BackTriggerState {
-                state,
-                axis,
-                effect,
-                status,
-                stop,
+            let index = value[37] & INDEX_MASK;
+            let touching = mask_shift(value[37], TOUCHING_MASK) == 0;
+            let x = u16::from_le_bytes([value[38], value[39] & X_MASK]);
+            let y = (mask_shift(value[39], Y_MASK) as u16) | (value[40] as u16) << 4;
+            let two = FingerData {
+                index,
+                is_touching: touching,
+                x,
+                y,
             };
 
-            BackTriggerGroup { l2, r2 }
+            [one, two]
         };
+        let timestamp = value[41];
 
-        let angular_velocity = AngularVelocityState {
-            x: i16::from_ne_bytes(value[16..=17].try_into().unwrap()),
-            y: i16::from_ne_bytes(value[20..=21].try_into().unwrap()),
-            z: i16::from_ne_bytes(value[18..=19].try_into().unwrap()),
+        TouchPadState {
+            state,
+            finger,
+            timestamp,
+        }
+    };
+
+    let front_triggers = {
+        const L1_MASK: u8 = 0b0000_0001;
+        const R1_MASK: u8 = 0b0000_0010;
+
+        let byte = value[9];
+
+        let l1 = ButtonState::from(mask_shift(byte, L1_MASK));
+        let r1 = ButtonState::from(mask_shift(byte, R1_MASK));
+
+        FrontTriggerGroup { l1, r1 }
+    };
+
+    let back_triggers = {
+        const L2_MASK: u8 = 0b0000_0100;
+        const L2_EFFECT_MASK: u8 = 0b1111_0000;
+        const L2_STATUS_MASK: u8 = 0b1111_0000;
+        const L2_STOP_MASK: u8 = 0b0000_1111;
+        const R2_MASK: u8 = 0b0000_1000;
+        const R2_EFFECT_MASK: u8 = 0b0000_1111;
+        const R2_STATUS_MASK: u8 = 0b1111_0000;
+        const R2_STOP_MASK: u8 = 0b0000_1111;
+
+        let state = ButtonState::from(mask_shift(value[9], L2_MASK));
+        let axis = Axis::new(value[5]);
+        let effect = BackTriggerEffect::from(mask_shift(value[48], L2_EFFECT_MASK));
+        let status = BackTriggerStatus::from((mask_shift(value[43], L2_STATUS_MASK), effect));
+        let stop = BackTriggerStop(mask_shift(value[43], L2_STOP_MASK));
+        let l2 = BackTriggerState {
+            state,
+            axis,
+            effect,
+            status,
+            stop,
         };
 
-        let acceleration = AccelerationState {
-            x: i16::from_ne_bytes(value[22..=23].try_into().unwrap()),
-            y: i16::from_ne_bytes(value[24..=25].try_into().unwrap()),
-            z: i16::from_ne_bytes(value[26..=27].try_into().unwrap()),
+        let state = ButtonState::from(mask_shift(value[9], R2_MASK));
+        let axis = Axis::new(value[6]);
+        let effect = BackTriggerEffect::from(mask_shift(value[48], R2_EFFECT_MASK));
+        let status = BackTriggerStatus::from((mask_shift(value[42], R2_STATUS_MASK), effect));
+        let stop = BackTriggerStop(mask_shift(value[42], R2_STOP_MASK));
+        let r2 = BackTriggerState {
+            state,
+            axis,
+            effect,
+            status,
+            stop,
         };
 
-        let plugged = {
-            const HEADPHONE_MASK: u8 = 0b0000_0001;
-            const HAPTIC_MASK: u8 = 0b0000_0010;
+        BackTriggerGroup { l2, r2 }
+    };
 
-            let headphone = PluggedState::from(mask_shift(value[54], HEADPHONE_MASK));
-            let microphone = {
-                const MICROPHONE_MASK: u8 = 0b0000_0010;
-                const MUTED_MASK: u8 = 0b0000_0100;
-                const EXTERNAL_MASK: u8 = 0b0000_0001;
+    let angular_velocity = AngularVelocityState {
+        x: i16::from_ne_bytes(value[16..=17].try_into().unwrap()),
+        y: i16::from_ne_bytes(value[20..=21].try_into().unwrap()),
+        z: i16::from_ne_bytes(value[18..=19].try_into().unwrap()),
+    };
 
-                let state = PluggedState::from(mask_shift(value[54], MICROPHONE_MASK));
-                let muted = MutedState::from(mask_shift(value[54], MUTED_MASK));
-                let external = mask_shift(value[55], EXTERNAL_MASK) != 0;
+    let acceleration = AccelerationState {
+        x: i16::from_ne_bytes(value[22..=23].try_into().unwrap()),
+        y: i16::from_ne_bytes(value[24..=25].try_into().unwrap()),
+        z: i16::from_ne_bytes(value[26..=27].try_into().unwrap()),
+    };
 
-                MicrophoneState {
-                    state,
-                    muted,
-                    external,
-                }
-            };
-            let usb = {
-                const DATA_MASK: u8 = 0b0000_1000;
-                const POWER_MASK: u8 = 0b0001_0000;
+    let sequence = value[7];
+    let sensor_timestamp = u32::from_le_bytes(value[28..=31].try_into().unwrap());
 
-                let data = PluggedState::from(mask_shift(value[54], DATA_MASK));
-                let power = PluggedState::from(mask_shift(value[54], POWER_MASK));
+    let plugged = {
+        const HEADPHONE_MASK: u8 = 0b0000_0001;
+        const HAPTIC_MASK: u8 = 0b0000_0010;
 
-                USBState { data, power }
-            };
-            let haptic_low_pass_filter = PluggedState::from(mask_shift(value[55], HAPTIC_MASK));
+        let headphone = PluggedState::from(mask_shift(value[54], HEADPHONE_MASK));
+        let microphone = {
+            const MICROPHONE_MASK: u8 = 0b0000_0010;
+            const MUTED_MASK: u8 = 0b0000_0100;
+            const EXTERNAL_MASK: u8 = 0b0000_0001;
 
-            PluggedGroup {
-                headphone,
-                microphone,
-                usb,
-                haptic_low_pass_filter,
+            let state = PluggedState::from(mask_shift(value[54], MICROPHONE_MASK));
+            let muted = MutedState::from(mask_shift(value[54], MUTED_MASK));
+            let external = mask_shift(value[55], EXTERNAL_MASK) != 0;
+
+            MicrophoneState {
+                state,
+                muted,
+                external,
             }
         };
+        let usb = {
+            const DATA_MASK: u8 = 0b0000_1000;
+            const POWER_MASK: u8 = 0b0001_0000;
 
-        let temperature = TemperatureState::Celsius(i8::from_ne_bytes([value[32]]));
+            let data = PluggedState::from(mask_shift(value[54], DATA_MASK));
+            let power = PluggedState::from(mask_shift(value[54], POWER_MASK));
 
-        let power = {
-            const STATE_MASK: u8 = 0b1111_0000;
-            const PERCENT_MASK: u8 = 0b0000_1111;
-
-            let state = PowerState::from(mask_shift(value[53], STATE_MASK));
-            let percent = mask_shift(value[53], PERCENT_MASK);
-            PowerGroup { state, percent }
+            USBState { data, power }
         };
+        let haptic_low_pass_filter = PluggedState::from(mask_shift(value[55], HAPTIC_MASK));
+
+        PluggedGroup {
+            headphone,
+            microphone,
+            usb,
+            haptic_low_pass_filter,
+        }
+    };
+
+    let temperature = TemperatureState::Celsius(i8::from_ne_bytes([value[32]]));
+
+    let power = {
+        const STATE_MASK: u8 = 0b1111_0000;
+        const LEVEL_MASK: u8 = 0b0000_1111;
+
+        let state = PowerState::from(mask_shift(value[53], STATE_MASK));
+        let level = mask_shift(value[53], LEVEL_MASK);
+        PowerGroup { state, level }
+    };
+
+    Ok(DualSenseState {
+        sticks,
+        directional_pad,
+        action_buttons,
+        menus,
+        touchpad,
+        front_triggers,
+        back_triggers,
+        angular_velocity,
+        acceleration,
+        sensor_timestamp,
+        sequence,
+        plugged,
+        temperature,
+        power,
+        edge: None,
+        initialized: bytes[0] != 0x00,
+    })
+}
 
-        DualSenseState {
-            sticks,
-            directional_pad,
-            action_buttons,
-            menus,
-            touchpad,
-            front_triggers,
-            back_triggers,
-            angular_velocity,
-            acceleration,
-            plugged,
-            temperature,
-            power,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a USB report and a Bluetooth report with the same `inputs`, which are laid out as in
+    /// the USB report, without the ID.
+    fn reports(inputs: &[(usize, u8)]) -> (RawInputReportUSB, RawInputReportBT) {
+        let mut usb = [0_u8; 64];
+        usb[0] = 0x01;
+        usb[8] = 0x08;
+        for &(index, byte) in inputs {
+            usb[index] = byte;
         }
+
+        let mut bt = [0_u8; 78];
+        bt[0] = 0x31;
+        bt[2..65].copy_from_slice(&usb[1..]);
+
+        let bt = RawInputReportBT::from_slice(&bt).unwrap();
+        (RawInputReportUSB::new(usb), bt)
+    }
+
+    #[test]
+    fn bluetooth_report_parses_like_usb() {
+        let (usb, bt) = reports(&[
+            (1, 200),
+            (8, 0x08 | 0b0010_0000),
+            (9, 0b0000_0010),
+            (16, 0x40),
+            (53, 0x25),
+        ]);
+
+        let state = usb.parse(KnownController::DualSense).unwrap();
+        assert_eq!(bt.parse(KnownController::DualSense).unwrap(), state);
+        assert!(state.is_initialized());
+        assert_eq!(bt.to_usb().as_array(), usb.as_array());
+    }
+
+    #[test]
+    fn bluetooth_report_parses_edge_buttons() {
+        let (usb, bt) = reports(&[(10, 0b0101_0000)]);
+
+        let edge = bt.parse(KnownController::DualSenseEdge).unwrap().edge();
+        assert_eq!(
+            edge,
+            usb.parse(KnownController::DualSenseEdge).unwrap().edge()
+        );
+        assert!(edge.unwrap().left_paddle().is_pressed());
+    }
+
+    #[test]
+    fn short_report_is_unsupported() {
+        assert!(matches!(
+            parse_report(&[0x31; 64], 1),
+            Err(Error::UnsupportedReport { id: 0x31, len: 64 })
+        ));
     }
 }
@@ -0,0 +1,295 @@
+//! The generic gamepad module.
+//!
+//! This module provides a minimal [`Gamepad`] trait, similar to the one used by other gamepad
+//! libraries (such as [`gilrs`]), along with an adapter that implements it for a [`DualSense`].
+//! This makes it possible to use the controller alongside other backends, without writing a
+//! wrapper for it.
+//!
+//! The DualSense buttons are mapped to generic codes by their position in the controller, so
+//! `Cross` is [`ButtonCode::South`], `Circle` is [`ButtonCode::East`] and so on. The buttons that
+//! have no generic counterpart, the `Mute` button, the touchpad button and the extra buttons of
+//! the DualSense Edge, are not mapped. They are still available through [`controller`].
+//!
+//! [`gilrs`]: https://docs.rs/gilrs/latest/gilrs/
+//! [`DualSense`]: struct@crate::DualSense
+//! [`Gamepad`]: trait@crate::gamepad::Gamepad
+//! [`ButtonCode::South`]: crate::gamepad::ButtonCode::South
+//! [`ButtonCode::East`]: crate::gamepad::ButtonCode::East
+//! [`controller`]: fn@crate::gamepad::GamepadAdapter::controller
+
+use crate::mappings::Button;
+use crate::{DualSense, DualSenseState};
+
+use std::collections::VecDeque;
+
+/// A generic button of a gamepad.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonCode {
+    /// The bottom action button (`Cross`).
+    South,
+    /// The right action button (`Circle`).
+    East,
+    /// The top action button (`Triangle`).
+    North,
+    /// The left action button (`Square`).
+    West,
+    /// The left shoulder button (`L1`).
+    LeftTrigger,
+    /// The right shoulder button (`R1`).
+    RightTrigger,
+    /// The left back trigger, as a digital button (`L2`).
+    LeftTrigger2,
+    /// The right back trigger, as a digital button (`R2`).
+    RightTrigger2,
+    /// The select button (`Create`).
+    Select,
+    /// The start button (`Options`).
+    Start,
+    /// The mode button (`Home (PS)`).
+    Mode,
+    /// The left analog stick button (`L3`).
+    LeftThumb,
+    /// The right analog stick button (`R3`).
+    RightThumb,
+    /// The up arrow of the directional pad.
+    DPadUp,
+    /// The down arrow of the directional pad.
+    DPadDown,
+    /// The left arrow of the directional pad.
+    DPadLeft,
+    /// The right arrow of the directional pad.
+    DPadRight,
+}
+
+impl ButtonCode {
+    /// Get the DualSense button mapped to the code.
+    fn button(self) -> Button {
+        match self {
+            ButtonCode::South => Button::Cross,
+            ButtonCode::East => Button::Circle,
+            ButtonCode::North => Button::Triangle,
+            ButtonCode::West => Button::Square,
+            ButtonCode::LeftTrigger => Button::L1,
+            ButtonCode::RightTrigger => Button::R1,
+            ButtonCode::LeftTrigger2 => Button::L2,
+            ButtonCode::RightTrigger2 => Button::R2,
+            ButtonCode::Select => Button::Create,
+            ButtonCode::Start => Button::Options,
+            ButtonCode::Mode => Button::Home,
+            ButtonCode::LeftThumb => Button::L3,
+            ButtonCode::RightThumb => Button::R3,
+            ButtonCode::DPadUp => Button::DPadUp,
+            ButtonCode::DPadDown => Button::DPadDown,
+            ButtonCode::DPadLeft => Button::DPadLeft,
+            ButtonCode::DPadRight => Button::DPadRight,
+        }
+    }
+
+    /// Get the code mapped to the DualSense `button`, if there is one.
+    fn from_button(button: Button) -> Option<ButtonCode> {
+        let code = match button {
+            Button::Cross => ButtonCode::South,
+            Button::Circle => ButtonCode::East,
+            Button::Triangle => ButtonCode::North,
+            Button::Square => ButtonCode::West,
+            Button::L1 => ButtonCode::LeftTrigger,
+            Button::R1 => ButtonCode::RightTrigger,
+            Button::L2 => ButtonCode::LeftTrigger2,
+            Button::R2 => ButtonCode::RightTrigger2,
+            Button::Create => ButtonCode::Select,
+            Button::Options => ButtonCode::Start,
+            Button::Home => ButtonCode::Mode,
+            Button::L3 => ButtonCode::LeftThumb,
+            Button::R3 => ButtonCode::RightThumb,
+            Button::DPadUp => ButtonCode::DPadUp,
+            Button::DPadDown => ButtonCode::DPadDown,
+            Button::DPadLeft => ButtonCode::DPadLeft,
+            Button::DPadRight => ButtonCode::DPadRight,
+            Button::Mute | Button::TouchPad => return None,
+        };
+
+        Some(code)
+    }
+}
+
+/// A generic axis of a gamepad.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AxisCode {
+    /// The `X` axis of the left analog stick, from `-1.0` (left) to `1.0` (right).
+    LeftStickX,
+    /// The `Y` axis of the left analog stick, from `-1.0` (down) to `1.0` (up).
+    LeftStickY,
+    /// The `X` axis of the right analog stick, from `-1.0` (left) to `1.0` (right).
+    RightStickX,
+    /// The `Y` axis of the right analog stick, from `-1.0` (down) to `1.0` (up).
+    RightStickY,
+    /// The left back trigger (`L2`), from `0.0` to `1.0`.
+    LeftZ,
+    /// The right back trigger (`R2`), from `0.0` to `1.0`.
+    RightZ,
+}
+
+impl AxisCode {
+    /// Every axis.
+    const ALL: [AxisCode; 6] = [
+        AxisCode::LeftStickX,
+        AxisCode::LeftStickY,
+        AxisCode::RightStickX,
+        AxisCode::RightStickY,
+        AxisCode::LeftZ,
+        AxisCode::RightZ,
+    ];
+
+    /// Get the value of the axis in `state`.
+    fn value(self, state: &DualSenseState) -> f32 {
+        match self {
+            AxisCode::LeftStickX => state.left_stick().x_normalized(),
+            AxisCode::LeftStickY => state.left_stick().y_normalized(),
+            AxisCode::RightStickX => state.right_stick().x_normalized(),
+            AxisCode::RightStickY => state.right_stick().y_normalized(),
+            AxisCode::LeftZ => state.l2().axis_normalized(),
+            AxisCode::RightZ => state.r2().axis_normalized(),
+        }
+    }
+}
+
+/// An input event of a gamepad.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    /// A button was pressed.
+    ButtonPressed(ButtonCode),
+    /// A button was released.
+    ButtonReleased(ButtonCode),
+    /// An axis changed to the given value.
+    AxisChanged(AxisCode, f32),
+}
+
+/// A minimal, generic gamepad.
+pub trait Gamepad {
+    /// Return `true` if the button is pressed and `false` otherwise.
+    fn button(&self, code: ButtonCode) -> bool;
+
+    /// Get the current value of the axis.
+    fn axis(&self, code: AxisCode) -> f32;
+
+    /// Get the next input event, if there is one.
+    ///
+    /// This method never blocks.
+    fn poll_event(&mut self) -> Option<Event>;
+}
+
+/// A [`DualSense`] adapted to the [`Gamepad`] trait.
+///
+/// The adapter checks the controller for new states without blocking, turning the changes
+/// between them into events:
+///
+/// ```rust
+/// # #[cfg(feature = "mock")]
+/// # {
+/// use duplosentido::gamepad::{ButtonCode, Event, Gamepad, GamepadAdapter};
+/// use duplosentido::{mock::MockDevice, DualSense};
+///
+/// let device = MockDevice::new();
+/// let mut gamepad = GamepadAdapter::new(DualSense::mock(device.clone()).unwrap());
+/// assert_eq!(gamepad.poll_event(), None);
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[1..=4].fill(128);
+/// report[8] = 0x08 | 0b0010_0000; // Cross pressed.
+/// device.push_report(&report);
+///
+/// assert_eq!(gamepad.poll_event(), Some(Event::ButtonPressed(ButtonCode::South)));
+/// assert_eq!(gamepad.poll_event(), None);
+/// assert!(gamepad.button(ButtonCode::South));
+/// # }
+/// ```
+///
+/// # Errors
+/// The [`Gamepad`] trait has no notion of errors, so any error while updating the controller
+/// (e.g. a disconnection) is treated as having no new events. To handle them, use the controller
+/// directly through [`controller`].
+///
+/// [`DualSense`]: struct@crate::DualSense
+/// [`Gamepad`]: trait@crate::gamepad::Gamepad
+/// [`controller`]: fn@Self::controller
+pub struct GamepadAdapter {
+    controller: DualSense,
+    /// The state the pending events were generated from.
+    previous: DualSenseState,
+    pending: VecDeque<Event>,
+}
+
+impl GamepadAdapter {
+    /// Adapt the `controller` to the [`Gamepad`] trait.
+    ///
+    /// [`Gamepad`]: trait@crate::gamepad::Gamepad
+    pub fn new(controller: DualSense) -> Self {
+        let previous = controller.state();
+
+        GamepadAdapter {
+            controller,
+            previous,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Get the adapted controller.
+    pub fn controller(&self) -> &DualSense {
+        &self.controller
+    }
+
+    /// Stop adapting the controller, returning it back.
+    pub fn into_inner(self) -> DualSense {
+        self.controller
+    }
+
+    /// Queue the events between the previous state and `state`.
+    fn queue_events(&mut self, state: DualSenseState) {
+        let code = |buttons: Vec<Button>| {
+            buttons
+                .into_iter()
+                .filter_map(ButtonCode::from_button)
+                .collect::<Vec<_>>()
+        };
+
+        for code in code(state.just_pressed(&self.previous)) {
+            self.pending.push_back(Event::ButtonPressed(code));
+        }
+        for code in code(state.just_released(&self.previous)) {
+            self.pending.push_back(Event::ButtonReleased(code));
+        }
+        for axis in AxisCode::ALL {
+            let value = axis.value(&state);
+            if value != axis.value(&self.previous) {
+                self.pending.push_back(Event::AxisChanged(axis, value));
+            }
+        }
+
+        self.previous = state;
+    }
+}
+
+impl Gamepad for GamepadAdapter {
+    fn button(&self, code: ButtonCode) -> bool {
+        self.controller.state().button(code.button()).is_pressed()
+    }
+
+    fn axis(&self, code: AxisCode) -> f32 {
+        code.value(&self.controller.state())
+    }
+
+    fn poll_event(&mut self) -> Option<Event> {
+        while self.pending.is_empty() {
+            match self.controller.poll() {
+                Ok(Some(state)) => self.queue_events(state),
+                _ => return None,
+            }
+        }
+
+        self.pending.pop_front()
+    }
+}
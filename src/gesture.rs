@@ -0,0 +1,334 @@
+//! The gesture detection module.
+//!
+//! A gesture is an input that spans multiple states, such as shaking the controller or swiping
+//! a finger across the touchpad. The
+//! detectors in this module must be fed every new state, in the order they were read, and report
+//! when a gesture happened.
+
+use crate::mappings::TouchPadState;
+use crate::DualSenseState;
+
+use std::time::Duration;
+
+/// The default minimum acceleration of a shake, in units of g.
+const DEFAULT_SHAKE_THRESHOLD: f32 = 2.5;
+
+/// The default minimum time between two shakes.
+const DEFAULT_SHAKE_COOLDOWN: Duration = Duration::from_millis(500);
+
+/// The default minimum distance of a swipe, as a fraction of the touchpad size.
+const DEFAULT_SWIPE_DISTANCE: f32 = 0.3;
+
+/// The default maximum duration of a swipe.
+const DEFAULT_SWIPE_DURATION: Duration = Duration::from_millis(500);
+
+/// A detector of the controller being shaken.
+///
+/// A shake is detected when the magnitude of the acceleration goes over a threshold (`2.5` g by
+/// default). Since a single vigorous shake produces multiple spikes, every spike during a
+/// cooldown (500ms by default) after a shake is part of it, and the acceleration must also drop
+/// below the threshold before another shake is detected. The time between states is computed
+/// from their [`sensor_delta`], so the detector is deterministic:
+///
+/// ```rust
+/// use duplosentido::gesture::ShakeDetector;
+/// use duplosentido::DualSenseState;
+///
+/// // A state read `ms` milliseconds after the first one, with an acceleration of `g` units of g.
+/// let state = |g: i16, ms: u32| {
+///     let mut report = [0_u8; 64];
+///     report[0] = 0x01;
+///     report[8] = 0x08;
+///     report[24..=25].copy_from_slice(&(g * 8192).to_le_bytes());
+///     report[28..=31].copy_from_slice(&(ms * 3000).to_le_bytes());
+///     DualSenseState::from_raw(report).unwrap()
+/// };
+///
+/// let mut detector = ShakeDetector::new();
+/// assert!(!detector.shaken(&state(1, 0)));
+///
+/// // The shake is detected once, even if the spike lasts for multiple states.
+/// assert!(detector.shaken(&state(3, 4)));
+/// assert!(!detector.shaken(&state(3, 8)));
+///
+/// // Spikes right after it are still the same shake.
+/// assert!(!detector.shaken(&state(1, 50)));
+/// assert!(!detector.shaken(&state(3, 100)));
+///
+/// // After the cooldown, a new shake is detected.
+/// assert!(!detector.shaken(&state(1, 700)));
+/// assert!(detector.shaken(&state(3, 704)));
+/// ```
+///
+/// [`sensor_delta`]: fn@crate::DualSenseState::sensor_delta
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ShakeDetector {
+    threshold: f32,
+    cooldown: Duration,
+    previous: Option<DualSenseState>,
+    /// The time elapsed since the last shake, if there was one.
+    since_shake: Option<Duration>,
+    /// If the acceleration dropped below the threshold since the last shake.
+    armed: bool,
+}
+
+impl ShakeDetector {
+    /// Create a shake detector with the default threshold (`2.5` g) and cooldown (500ms).
+    pub fn new() -> Self {
+        ShakeDetector::with_settings(DEFAULT_SHAKE_THRESHOLD, DEFAULT_SHAKE_COOLDOWN)
+    }
+
+    /// Create a shake detector with the given `threshold`, in units of g, and `cooldown`.
+    ///
+    /// See [`ShakeDetector`] for more information.
+    ///
+    /// [`ShakeDetector`]: struct@crate::gesture::ShakeDetector
+    pub fn with_settings(threshold: f32, cooldown: Duration) -> Self {
+        ShakeDetector {
+            threshold,
+            cooldown,
+            previous: None,
+            since_shake: None,
+            armed: true,
+        }
+    }
+
+    /// Get the minimum acceleration of a shake, in units of g.
+    pub fn threshold(&self) -> f32 {
+        self.threshold
+    }
+
+    /// Get the minimum time between two shakes.
+    pub fn cooldown(&self) -> Duration {
+        self.cooldown
+    }
+
+    /// Feed a new controller state to the detector, returning `true` if a new shake started and
+    /// `false` otherwise.
+    pub fn shaken(&mut self, state: &DualSenseState) -> bool {
+        let elapsed = self
+            .previous
+            .replace(*state)
+            .map_or(Duration::ZERO, |previous| state.sensor_delta(&previous));
+        if let Some(since_shake) = &mut self.since_shake {
+            *since_shake += elapsed;
+        }
+
+        if state.acceleration().magnitude_g() < self.threshold {
+            self.armed = true;
+            return false;
+        }
+        if !self.armed || self.since_shake.is_some_and(|since| since < self.cooldown) {
+            return false;
+        }
+
+        self.since_shake = Some(Duration::ZERO);
+        self.armed = false;
+
+        true
+    }
+}
+
+impl Default for ShakeDetector {
+    fn default() -> Self {
+        ShakeDetector::new()
+    }
+}
+
+/// The direction of a swipe.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SwipeDirection {
+    /// A swipe towards the top of the touchpad.
+    Up,
+    /// A swipe towards the bottom of the touchpad.
+    Down,
+    /// A swipe towards the left of the touchpad.
+    Left,
+    /// A swipe towards the right of the touchpad.
+    Right,
+}
+
+/// A touch being followed by a [`SwipeDetector`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Swipe {
+    /// The index of the finger doing the swipe.
+    index: u8,
+    /// The normalized position where the touch started.
+    start: (f32, f32),
+    /// The normalized position of the latest touch.
+    end: (f32, f32),
+    /// The time elapsed since the touch started.
+    duration: Duration,
+}
+
+/// A detector of swipes on the touchpad.
+///
+/// A swipe is a single finger touching the touchpad, moving at least a minimum distance (`0.3` of
+/// the touchpad size by default) and lifting in at most a maximum duration (500ms by default).
+/// Slower drags and touches with more than one finger are not swipes. The direction of the swipe
+/// is the one in which the finger moved the most.
+///
+/// The detector must be updated with every new touchpad state, along with the time elapsed since
+/// the previous update, just like a [`ButtonTracker`]:
+///
+/// ```rust
+/// use duplosentido::gesture::{SwipeDetector, SwipeDirection};
+/// use duplosentido::DualSenseState;
+/// use std::time::Duration;
+///
+/// // A touchpad state with the finger `index` at (`x`, `y`), if it is touching.
+/// let touchpad = |index: u8, touch: Option<(u16, u16)>| {
+///     let (x, y) = touch.unwrap_or((0, 0));
+///     let mut report = [0_u8; 64];
+///     report[0] = 0x01;
+///     report[8] = 0x08;
+///     report[33] = index | if touch.is_some() { 0x00 } else { 0x80 };
+///     report[34] = x as u8;
+///     report[35] = (x >> 8) as u8 | (y << 4) as u8;
+///     report[36] = (y >> 4) as u8;
+///     report[37] = 0x80;
+///     DualSenseState::from_raw(report).unwrap().touchpad()
+/// };
+///
+/// let mut detector = SwipeDetector::new();
+/// let frame = Duration::from_millis(50);
+///
+/// // Swipe to the right, across most of the touchpad.
+/// assert_eq!(detector.swiped(&touchpad(1, Some((200, 540))), frame), None);
+/// assert_eq!(detector.swiped(&touchpad(1, Some((1000, 560))), frame), None);
+/// assert_eq!(detector.swiped(&touchpad(1, Some((1700, 520))), frame), None);
+/// assert_eq!(detector.swiped(&touchpad(1, None), frame), Some(SwipeDirection::Right));
+///
+/// // Slowly drag up, which is not a swipe.
+/// assert_eq!(detector.swiped(&touchpad(2, Some((960, 1000))), frame), None);
+/// for _ in 0..20 {
+///     assert_eq!(detector.swiped(&touchpad(2, Some((960, 100))), frame), None);
+/// }
+/// assert_eq!(detector.swiped(&touchpad(2, None), frame), None);
+/// ```
+///
+/// [`ButtonTracker`]: struct@crate::tracker::ButtonTracker
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SwipeDetector {
+    min_distance: f32,
+    max_duration: Duration,
+    /// The touch being followed, if it can still be a swipe.
+    swipe: Option<Swipe>,
+    /// The index of the last finger touching the touchpad, to not follow it again.
+    last_index: Option<u8>,
+}
+
+impl SwipeDetector {
+    /// Create a swipe detector with the default minimum distance (`0.3`) and maximum duration
+    /// (500ms).
+    pub fn new() -> Self {
+        SwipeDetector::with_settings(DEFAULT_SWIPE_DISTANCE, DEFAULT_SWIPE_DURATION)
+    }
+
+    /// Create a swipe detector with the given `min_distance` and `max_duration`.
+    ///
+    /// The `min_distance` is a fraction of the touchpad size along the direction of the swipe, so
+    /// `1.0` is a swipe from one edge to the other. See [`SwipeDetector`] for more information.
+    ///
+    /// [`SwipeDetector`]: struct@crate::gesture::SwipeDetector
+    pub fn with_settings(min_distance: f32, max_duration: Duration) -> Self {
+        SwipeDetector {
+            min_distance,
+            max_duration,
+            swipe: None,
+            last_index: None,
+        }
+    }
+
+    /// Get the minimum distance of a swipe, as a fraction of the touchpad size.
+    pub fn min_distance(&self) -> f32 {
+        self.min_distance
+    }
+
+    /// Get the maximum duration of a swipe.
+    pub fn max_duration(&self) -> Duration {
+        self.max_duration
+    }
+
+    /// Feed a new `touchpad` state to the detector, read `elapsed` after the previous one.
+    ///
+    /// Returns the direction of the swipe if one was completed (i.e. the finger was lifted) and
+    /// `None` otherwise.
+    pub fn swiped(
+        &mut self,
+        touchpad: &TouchPadState,
+        elapsed: Duration,
+    ) -> Option<SwipeDirection> {
+        let mut touches = touchpad.touches();
+        let touch = match (touches.next(), touches.next()) {
+            (Some(touch), None) => Some(touch),
+            // A second finger cancels the swipe.
+            (Some(_), Some(_)) => {
+                self.swipe = None;
+                self.last_index = None;
+                return None;
+            }
+            _ => None,
+        };
+
+        if let Some(swipe) = &mut self.swipe {
+            swipe.duration += elapsed;
+            if swipe.duration > self.max_duration {
+                self.swipe = None;
+            }
+        }
+
+        match (touch, self.swipe) {
+            (Some(touch), Some(mut swipe)) if touch.index() == swipe.index => {
+                swipe.end = (touch.x_normalized(), touch.y_normalized());
+                self.swipe = Some(swipe);
+
+                None
+            }
+            (touch, swipe) => {
+                self.swipe = touch
+                    .filter(|touch| self.last_index != Some(touch.index()))
+                    .map(|touch| Swipe {
+                        index: touch.index(),
+                        start: (touch.x_normalized(), touch.y_normalized()),
+                        end: (touch.x_normalized(), touch.y_normalized()),
+                        duration: Duration::ZERO,
+                    });
+                self.last_index = touch.map(|touch| touch.index());
+
+                swipe.and_then(|swipe| self.direction(&swipe))
+            }
+        }
+    }
+
+    /// Get the direction of a completed `swipe`, if it was long enough.
+    fn direction(&self, swipe: &Swipe) -> Option<SwipeDirection> {
+        let dx = swipe.end.0 - swipe.start.0;
+        let dy = swipe.end.1 - swipe.start.1;
+
+        let (distance, direction) = if dx.abs() >= dy.abs() {
+            let direction = if dx > 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            };
+            (dx.abs(), direction)
+        } else {
+            let direction = if dy > 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            };
+            (dy.abs(), direction)
+        };
+
+        (distance >= self.min_distance).then_some(direction)
+    }
+}
+
+impl Default for SwipeDetector {
+    fn default() -> Self {
+        SwipeDetector::new()
+    }
+}
@@ -9,16 +9,36 @@
 //! module *does not* aim to be a replacement for a full implementation like other known crates.
 //! The goal of not using such crates is to minimize dependencies while still being able to
 //! interact with the HIDAPI defined functions.
+//!
+//! # Backends
+//! By default, the devices are accessed through the HIDAPI library. On Linux, the `linux-hidraw`
+//! feature replaces it by a backend that talks to the hidraw devices directly, removing the need
+//! for the C library. Both backends expose the same [`DeviceWrapper`], so the rest of the crate
+//! does not depend on the selected one.
+//!
+//! [`DeviceWrapper`]: struct@crate::hidapi::DeviceWrapper
 
 // TODO: Improve platform support (MacOS, Windows, Linux, FreeBSD).
 // TODO: Improve error types (mirror HID error messages).
 
+#[cfg(not(all(target_os = "linux", feature = "linux-hidraw")))]
 mod ffi;
+#[cfg(not(all(target_os = "linux", feature = "linux-hidraw")))]
+mod libhidapi;
+#[cfg(all(target_os = "linux", feature = "linux-hidraw"))]
+mod linux;
+
+#[cfg(not(all(target_os = "linux", feature = "linux-hidraw")))]
+pub(crate) use libhidapi::{enumerate, exit, DeviceWrapper};
+#[cfg(all(target_os = "linux", feature = "linux-hidraw"))]
+pub(crate) use linux::{enumerate, exit, DeviceWrapper};
 
+use crate::info::Connection;
 use crate::Mode;
 
 use libc::c_int;
-use std::ptr;
+#[cfg(target_os = "linux")]
+use std::os::fd::RawFd;
 use thiserror::Error;
 
 /// A HID device vendor ID.
@@ -57,82 +77,71 @@ impl ProductID {
     }
 }
 
-/// A wrapper for a HIDAPI HID Device.
-pub(crate) struct DeviceWrapper {
-    device: *mut ffi::hid_device,
+/// A string describing a HID device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum DeviceString {
+    Manufacturer,
+    Product,
+    SerialNumber,
 }
 
-impl DeviceWrapper {
-    /// Try to open a HID device.
-    ///
-    /// This function can fail. The most common reason is if a device with the supplied `vendor_id`
-    /// and `product_id` was not found.
-    pub(crate) fn open(vendor_id: VendorID, product_id: ProductID) -> Result<Self, Error> {
-        // SAFETY: This is safe since we only supply `unsigned short` variables to the function.
-        // The function `hid_open` returns a `null` pointer in the fail case. This is handled in
-        // the `if` below.
-        let device = unsafe { ffi::hid_open(vendor_id.id(), product_id.id(), ptr::null()) };
-        if device.is_null() {
-            return Err(Error::Open);
-        }
-
-        Ok(DeviceWrapper { device })
-    }
+/// A HID device found while enumerating, without opening it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EnumeratedDevice {
+    /// The platform-specific path of the device, which can be used to open it.
+    pub(crate) path: String,
+    /// The serial number of the device, which is empty if it does not report one.
+    pub(crate) serial_number: String,
+    /// How the device is connected.
+    pub(crate) connection: Connection,
+}
 
+/// Count the HID devices with the supplied `vendor_id` and `product_id`, without opening them.
+pub(crate) fn count(vendor_id: VendorID, product_id: ProductID) -> usize {
+    enumerate(vendor_id, product_id).len()
+}
+
+/// A HID device able to communicate with the crate.
+///
+/// This trait abstracts the operations used by a [`DualSense`] controller, making it possible to
+/// replace the real HIDAPI device by other implementations (e.g. a mock device).
+///
+/// Devices must be [`Send`], so a controller can be moved to another thread.
+///
+/// [`DualSense`]: struct@crate::DualSense
+pub(crate) trait HidDevice: Send {
     /// Set the HID device mode to be either _blocking_ or _non-blocking_.
+    fn set_mode(&self, mode: Mode) -> Result<(), Error>;
+
+    /// Read data from a HID device to `buf`, returning the number of bytes read.
+    fn read(&self, buf: &mut [u8]) -> Result<usize, Error>;
+
+    /// Read data from a HID device to `buf`, waiting at most `milliseconds` for it.
     ///
-    /// See the [`Mode`] enum for more information.
-    ///
-    /// [`Mode`]: enum@crate::Mode
-    pub(crate) fn set_mode(&self, mode: Mode) -> Result<(), Error> {
-        // SAFETY: This function is safe to call since the device is guaranteed to be not `null`,
-        // as the only way to get one is by calling `open`, and we check if the pointer is valid
-        // during it. Also, the conversion of `Mode` to `c_int` is safe since the enum has
-        // well-defined values (0 for blocking and 1 for non-blocking). This function returns `-1`
-        // in case of an error and `0` otherwise.
-        match unsafe { ffi::hid_set_nonblocking(self.device, mode as c_int) } {
-            -1 => Err(Error::Mode),
-            0 => Ok(()),
-            _ => unreachable!(),
-        }
-    }
+    /// A `milliseconds` value of `-1` blocks until data is available.
+    fn read_timeout(&self, buf: &mut [u8], milliseconds: c_int) -> Result<usize, Error>;
+
+    /// Write the report in `buf` to a HID device, returning the number of bytes written.
+    fn write(&self, buf: &[u8]) -> Result<usize, Error>;
 
-    /// Read data from a HID device to `buf`.
+    /// Get a feature report from a HID device to `buf`, returning the number of bytes read.
     ///
-    /// This function returns the number of bytes read in case of success.
-    pub(crate) fn read(&self, buf: &mut [u8]) -> Result<usize, Error> {
-        // SAFETY: This function is safe to call since the device is guaranteed to be not `null`,
-        // as the only way to get one is by calling `open`, and we check if the pointer is valid
-        // during it. Also, the slice `buf` outlives the created mutable pointer. It is also
-        // guaranteed to not have buffer overflows since we pass the correct buffer length to it.
-        match unsafe { ffi::hid_read(self.device, buf.as_mut_ptr(), buf.len()) } {
-            -1 => Err(Error::Read),
-            bytes => Ok(bytes as usize),
-        }
-    }
-}
+    /// The first byte of `buf` must be set to the ID of the requested report.
+    fn get_feature_report(&self, buf: &mut [u8]) -> Result<usize, Error>;
 
-impl Drop for DeviceWrapper {
-    fn drop(&mut self) {
-        // SAFETY: This is safe, since we know `self.device` is a valid device, as the only way to
-        // get one is by calling `open`, and we check if the pointer is valid during it.
-        unsafe {
-            ffi::hid_close(self.device);
-        }
-    }
-}
+    /// Get one of the strings describing the HID device.
+    fn get_string(&self, string: DeviceString) -> Result<String, Error>;
 
-/// Finalize the HIDAPI library.
-///
-/// This function frees all of the static data associated with `HIDAPI`. It should be called when
-/// the `HIDAPI` library is not needed anymore to avoid memory leaks.
-pub(crate) fn exit() -> Result<(), Error> {
-    // SAFETY: This function is safe to call since we handle all the possible cases (`-1` for error
-    // and `0` for success).
-    match unsafe { ffi::hid_exit() } {
-        -1 => Err(Error::Exit),
-        0 => Ok(()),
-        _ => unreachable!(),
+    /// Open the same device again, returning a new handle to it.
+    ///
+    /// This is used to recover from a disconnection, so the new handle should target the same
+    /// physical device whenever possible.
+    fn reopen(&self) -> Result<Box<dyn HidDevice>, Error>;
+
+    /// Get the file descriptor of the device, if there is one.
+    #[cfg(target_os = "linux")]
+    fn raw_fd(&self) -> Option<RawFd> {
+        None
     }
 }
 
@@ -146,11 +155,43 @@ impl RawInputReportUSB {
         RawInputReportUSB(slice)
     }
 
+    pub(crate) fn from_slice(slice: &[u8]) -> Option<Self> {
+        slice.try_into().ok().map(RawInputReportUSB)
+    }
+
     pub(crate) fn as_array(&self) -> &[u8; 64] {
         &self.0
     }
 }
 
+/// A raw representation of an input report from a DualSense controller using a Bluetooth
+/// connection.
+///
+/// The report has the same inputs as the USB one, but they start one byte later, since the report
+/// ID is followed by a sequence number. It also ends with a CRC32 checksum.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct RawInputReportBT([u8; 78]);
+
+impl RawInputReportBT {
+    pub(crate) fn from_slice(slice: &[u8]) -> Option<Self> {
+        slice.try_into().ok().map(RawInputReportBT)
+    }
+
+    pub(crate) fn as_array(&self) -> &[u8; 78] {
+        &self.0
+    }
+
+    /// Move the inputs to where they are in the USB report, dropping the Bluetooth-only bytes.
+    pub(crate) fn to_usb(self) -> RawInputReportUSB {
+        let mut report = [0_u8; 64];
+        report[0] = 0x01;
+        report[1..].copy_from_slice(&self.0[2..65]);
+
+        RawInputReportUSB(report)
+    }
+}
+
 /// The error type for operations with a HID device.
 #[derive(Error, Debug)]
 pub(crate) enum Error {
@@ -170,10 +211,32 @@ pub(crate) enum Error {
     /// This error can happen when trying to read from a HID device.
     #[error("Could not read HID device")]
     Read,
+    /// A disconnection error.
+    ///
+    /// This error can happen when trying to read from a HID device that was disconnected.
+    #[error("HID device was disconnected")]
+    Disconnected,
+    /// A write error.
+    ///
+    /// This error can happen when trying to write to a HID device.
+    #[error("Could not write to HID device")]
+    Write,
+    /// A feature report error.
+    ///
+    /// This error can happen when trying to get a feature report from a HID device.
+    #[error("Could not get feature report from HID device")]
+    FeatureReport,
+    /// A device string error.
+    ///
+    /// This error can happen when trying to get a string (e.g. the product name) from a HID
+    /// device.
+    #[error("Could not get string from HID device")]
+    DeviceString,
     /// An exit error.
     ///
     /// This error can happen when trying to finish using the controller (usually when dropping
     /// it).
     #[error("Could not properly clean up at controller exit")]
+    #[cfg_attr(all(target_os = "linux", feature = "linux-hidraw"), allow(dead_code))]
     Exit,
 }
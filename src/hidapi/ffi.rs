@@ -1,4 +1,4 @@
-use libc::{c_int, c_uchar, c_ushort, c_void, size_t, wchar_t};
+use libc::{c_char, c_int, c_uchar, c_ushort, c_void, size_t, wchar_t};
 use std::marker::{PhantomData, PhantomPinned};
 
 #[repr(C)]
@@ -9,8 +9,65 @@ pub(super) struct hid_device {
     _marker: PhantomData<(*mut u8, PhantomPinned)>,
 }
 
+/// hidapi info structure
+///
+/// Only the fields up to `next` are declared, since newer versions of HIDAPI append fields to the
+/// end of the structure, and it is never allocated by the crate.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub(super) struct hid_device_info {
+    /// Platform-specific device path
+    pub(super) path: *mut c_char,
+    /// Device Vendor ID
+    pub(super) vendor_id: c_ushort,
+    /// Device Product ID
+    pub(super) product_id: c_ushort,
+    /// Serial Number
+    pub(super) serial_number: *mut wchar_t,
+    /// Device Release Number in binary-coded decimal, also known as Device Version Number
+    pub(super) release_number: c_ushort,
+    /// Manufacturer String
+    pub(super) manufacturer_string: *mut wchar_t,
+    /// Product string
+    pub(super) product_string: *mut wchar_t,
+    /// Usage Page for this Device/Interface (Windows/Mac/hidraw only)
+    pub(super) usage_page: c_ushort,
+    /// Usage for this Device/Interface (Windows/Mac/hidraw only)
+    pub(super) usage: c_ushort,
+    /// The USB interface which this logical device represents.
+    pub(super) interface_number: c_int,
+    /// Pointer to the next device
+    pub(super) next: *mut hid_device_info,
+}
+
 // All documentation extracted directly from the HIDAPI source code.
 extern "C" {
+    /// @brief Enumerate the HID Devices.
+    ///
+    /// This function returns a linked list of all the HID devices attached to the system which
+    /// match vendor_id and product_id. If @p vendor_id is set to 0 then any vendor matches. If
+    /// @p product_id is set to 0 then any product matches. If @p vendor_id and @p product_id are
+    /// both set to 0, then all HID devices will be returned.
+    ///
+    /// @param vendor_id The Vendor ID (VID) of the types of device to open.
+    /// @param product_id The Product ID (PID) of the types of device to open.
+    ///
+    /// @returns This function returns a pointer to a linked list of type struct #hid_device_info,
+    /// containing information about the HID devices attached to the system, or NULL in the case of
+    /// failure or if no HID devices present in the system. Call hid_error(NULL) to get the failure
+    /// reason.
+    ///
+    /// @note The returned value by this function must to be freed by calling
+    /// hid_free_enumeration(), when not needed anymore.
+    pub(super) fn hid_enumerate(vendor_id: c_ushort, product_id: c_ushort) -> *mut hid_device_info;
+
+    /// @brief Free an enumeration Linked List
+    ///
+    /// This function frees a linked list created by hid_enumerate().
+    ///
+    /// @param devs Pointer to a list of struct_device returned from hid_enumerate().
+    pub(super) fn hid_free_enumeration(devs: *mut hid_device_info) -> c_void;
+
     /// @brief Open a HID device using a Vendor ID (VID), Product ID (PID) and optionally a
     /// serial number.
     ///
@@ -30,11 +87,42 @@ extern "C" {
         serial_number: *const wchar_t,
     ) -> *mut hid_device;
 
+    /// @brief Open a HID device by its path name.
+    ///
+    /// The path name be determined by calling hid_enumerate(), or a platform-specific path name
+    /// can be used (eg: /dev/hidraw0 on Linux).
+    ///
+    /// @param path The path name of the device to open
+    ///
+    /// @returns This function returns a pointer to a #hid_device object on success or NULL on
+    /// failure. Call hid_error(NULL) to get the failure reason.
+    ///
+    /// @note The returned object must be freed by calling hid_close(), when not needed anymore.
+    pub(super) fn hid_open_path(path: *const c_char) -> *mut hid_device;
+
     /// @brief Close a HID device.
     ///
     /// @param dev A device handle returned from hid_open().
     pub(super) fn hid_close(dev: *mut hid_device) -> c_void;
 
+    /// @brief Write an Output report to a HID device.
+    ///
+    /// The first byte of @p data[] must contain the Report ID. For devices which only support a
+    /// single report, this must be set to 0x0. The remaining bytes contain the report data. Since
+    /// the Report ID is mandatory, calls to hid_write() will always contain one more byte than the
+    /// report contains.
+    ///
+    /// hid_write() will send the data on the first OUT endpoint, if one exists. If it does not, it
+    /// will send the data through the Control Endpoint (Endpoint 0).
+    ///
+    /// @param dev A device handle returned from hid_open().
+    /// @param data The data to send, including the report number as the first byte.
+    /// @param length The length in bytes of the data to send.
+    ///
+    /// @returns This function returns the actual number of bytes written and -1 on error.
+    /// Call hid_error(dev) to get the failure reason.
+    pub(super) fn hid_write(dev: *mut hid_device, data: *const c_uchar, length: size_t) -> c_int;
+
     /// @brief Read an Input report from a HID device.
     ///
     /// Input reports are returned to the host through the INTERRUPT IN endpoint. The first byte
@@ -50,6 +138,48 @@ extern "C" {
     /// the handle is in non-blocking mode, this function returns 0.
     pub(super) fn hid_read(dev: *mut hid_device, data: *mut c_uchar, length: size_t) -> c_int;
 
+    /// @brief Read an Input report from a HID device with timeout.
+    ///
+    /// Input reports are returned to the host through the INTERRUPT IN endpoint. The first byte
+    /// will contain the Report number if the device uses numbered reports.
+    ///
+    /// @param dev A device handle returned from hid_open().
+    /// @param data A buffer to put the read data into.
+    /// @param length The number of bytes to read. For devices with multiple reports, make sure to
+    /// read an extra byte for the report number.
+    /// @param milliseconds timeout in milliseconds or -1 for blocking wait.
+    ///
+    /// @returns This function returns the actual number of bytes read and -1 on error.
+    /// Call hid_error(dev) to get the failure reason. If no packet was available to be read within
+    /// the timeout period, this function returns 0.
+    pub(super) fn hid_read_timeout(
+        dev: *mut hid_device,
+        data: *mut c_uchar,
+        length: size_t,
+        milliseconds: c_int,
+    ) -> c_int;
+
+    /// @brief Get a feature report from a HID device.
+    ///
+    /// Set the first byte of @p data[] to the Report ID of the report to be read. Make sure to
+    /// allow space for this extra byte in @p data[]. Upon return, the first byte will still
+    /// contain the Report ID, and the report data will start in data[1].
+    ///
+    /// @param dev A device handle returned from hid_open().
+    /// @param data A buffer to put the read data into, including the Report ID. Set the first
+    /// byte of @p data[] to the Report ID of the report to be read, or set it to zero if your
+    /// device does not use numbered reports.
+    /// @param length The number of bytes to read, including an extra byte for the report ID. The
+    /// buffer can be longer than the actual report.
+    ///
+    /// @returns This function returns the number of bytes read plus one for the report ID (which
+    /// is still in the first byte), or -1 on error. Call hid_error(dev) to get the failure reason.
+    pub(super) fn hid_get_feature_report(
+        dev: *mut hid_device,
+        data: *mut c_uchar,
+        length: size_t,
+    ) -> c_int;
+
     /// @brief Set the device handle to be non-blocking.
     ///
     /// In non-blocking mode calls to hid_read() will return immediately with a value of 0 if there
@@ -67,6 +197,65 @@ extern "C" {
     /// Call hid_error(dev) to get the failure reason.
     pub(super) fn hid_set_nonblocking(dev: *mut hid_device, nonblock: c_int) -> c_int;
 
+    /// @brief Get The Manufacturer String from a HID device.
+    ///
+    /// @param dev A device handle returned from hid_open().
+    /// @param string A wide string buffer to put the data into.
+    /// @param maxlen The length of the buffer in multiples of wchar_t.
+    ///
+    /// @returns This function returns 0 on success and -1 on error.
+    pub(super) fn hid_get_manufacturer_string(
+        dev: *mut hid_device,
+        string: *mut wchar_t,
+        maxlen: size_t,
+    ) -> c_int;
+
+    /// @brief Get The Product String from a HID device.
+    ///
+    /// @param dev A device handle returned from hid_open().
+    /// @param string A wide string buffer to put the data into.
+    /// @param maxlen The length of the buffer in multiples of wchar_t.
+    ///
+    /// @returns This function returns 0 on success and -1 on error.
+    pub(super) fn hid_get_product_string(
+        dev: *mut hid_device,
+        string: *mut wchar_t,
+        maxlen: size_t,
+    ) -> c_int;
+
+    /// @brief Get The Serial Number String from a HID device.
+    ///
+    /// @param dev A device handle returned from hid_open().
+    /// @param string A wide string buffer to put the data into.
+    /// @param maxlen The length of the buffer in multiples of wchar_t.
+    ///
+    /// @returns This function returns 0 on success and -1 on error.
+    pub(super) fn hid_get_serial_number_string(
+        dev: *mut hid_device,
+        string: *mut wchar_t,
+        maxlen: size_t,
+    ) -> c_int;
+
+    /// @brief Get a string describing the last error which occurred.
+    ///
+    /// This function is intended for logging/debugging purposes.
+    ///
+    /// This function guarantees to never return NULL. If there was no error in the last function
+    /// call - the returned string clearly indicates that.
+    ///
+    /// Any HIDAPI function that can explicitly indicate an execution failure (e.g. by an error
+    /// code, or by returning NULL) - may set the error string, to be returned by this function.
+    ///
+    /// Strings returned from hid_error() must not be freed by the user, i.e. owned by HIDAPI
+    /// library. Device-specific error string may remain allocated at most until hid_close() is
+    /// called. Global error string may remain allocated at most until hid_exit() is called.
+    ///
+    /// @param dev A device handle returned from hid_open(), or NULL to get the last
+    /// non-device-specific error (e.g. for errors in hid_open() or hid_enumerate()).
+    ///
+    /// @returns A string describing the last error (if any).
+    pub(super) fn hid_error(dev: *mut hid_device) -> *const wchar_t;
+
     /// @brief Finalize the HIDAPI library.
     ///
     /// This function frees all of the static data associated with HIDAPI. It should be called at
@@ -0,0 +1,408 @@
+//! The HIDAPI library backend.
+//!
+//! This backend links against the HIDAPI C library, which supports every major platform.
+
+use super::{ffi, DeviceString, EnumeratedDevice, Error, HidDevice, ProductID, VendorID};
+use crate::info::Connection;
+use crate::Mode;
+
+use libc::{c_int, wchar_t};
+use std::ffi::{CStr, CString};
+#[cfg(target_os = "linux")]
+use std::fs::File;
+#[cfg(target_os = "linux")]
+use std::os::fd::{AsRawFd, RawFd};
+use std::ptr;
+
+/// A wrapper for a HIDAPI HID Device.
+pub(crate) struct DeviceWrapper {
+    device: *mut ffi::hid_device,
+    vendor_id: VendorID,
+    product_id: ProductID,
+    // The null-terminated serial number of the device, used to open the same device again.
+    serial_number: Option<Vec<wchar_t>>,
+    // The path of the device, if it was opened by it, used to open the same device again when it
+    // has no serial number.
+    path: Option<CString>,
+    // The device node opened read-only by its path, when HIDAPI uses its hidraw backend. HIDAPI
+    // does not expose its own descriptor, so this one is used to wait for new reports instead.
+    #[cfg(target_os = "linux")]
+    node: Option<File>,
+}
+
+// SAFETY: A HIDAPI device handle is not bound to the thread that opened it, so it can be used
+// from any thread, as long as only one thread uses it at a time. Since `DeviceWrapper` owns the
+// pointer (it is never copied nor shared) and is not `Sync`, moving it to another thread moves
+// the only way of reaching the handle, upholding this invariant.
+unsafe impl Send for DeviceWrapper {}
+
+impl DeviceWrapper {
+    /// Try to open a HID device.
+    ///
+    /// This function can fail. The most common reason is if a device with the supplied `vendor_id`
+    /// and `product_id` was not found.
+    pub(crate) fn open(vendor_id: VendorID, product_id: ProductID) -> Result<Self, Error> {
+        DeviceWrapper::open_serial(vendor_id, product_id, None)
+    }
+
+    /// Try to open a HID device, optionally with a specific null-terminated `serial_number`.
+    ///
+    /// If `serial_number` is `None`, the first device with the supplied `vendor_id` and
+    /// `product_id` is opened.
+    fn open_serial(
+        vendor_id: VendorID,
+        product_id: ProductID,
+        serial_number: Option<&[wchar_t]>,
+    ) -> Result<Self, Error> {
+        let serial_ptr = serial_number.map_or(ptr::null(), <[wchar_t]>::as_ptr);
+
+        // SAFETY: This is safe since we only supply `unsigned short` variables to the function,
+        // along with either a `null` pointer or a pointer to a null-terminated wide string that
+        // outlives the call. The function `hid_open` returns a `null` pointer in the fail case.
+        // This is handled in `from_raw`.
+        let device = unsafe { ffi::hid_open(vendor_id.id(), product_id.id(), serial_ptr) };
+
+        DeviceWrapper::from_raw(device, vendor_id, product_id, None)
+    }
+
+    /// Try to open a HID device by its platform-specific `path`, as listed by [`enumerate`].
+    ///
+    /// The `vendor_id` and `product_id` must be the ones of the device, since they are used to
+    /// open it again if it has no serial number and its path changes.
+    ///
+    /// [`enumerate`]: fn@crate::hidapi::enumerate
+    pub(crate) fn open_path(
+        vendor_id: VendorID,
+        product_id: ProductID,
+        path: &str,
+    ) -> Result<Self, Error> {
+        let path = CString::new(path).map_err(|_| Error::Open)?;
+
+        // SAFETY: The path is a null-terminated string that outlives the call. The function
+        // `hid_open_path` returns a `null` pointer in the fail case. This is handled in
+        // `from_raw`.
+        let device = unsafe { ffi::hid_open_path(path.as_ptr()) };
+
+        DeviceWrapper::from_raw(device, vendor_id, product_id, Some(path))
+    }
+
+    /// Wrap a `device` returned by HIDAPI, failing if it is `null`.
+    fn from_raw(
+        device: *mut ffi::hid_device,
+        vendor_id: VendorID,
+        product_id: ProductID,
+        path: Option<CString>,
+    ) -> Result<Self, Error> {
+        if device.is_null() {
+            return Err(Error::Open);
+        }
+
+        // Some devices do not report a serial number, in which case the device is reopened by
+        // its path or IDs only.
+        let mut buffer: [wchar_t; 256] = [0; 256];
+        // SAFETY: The device was checked to not be `null` above, and the buffer length is passed
+        // along with it, so HIDAPI does not write past its end.
+        let serial_number =
+            match unsafe { ffi::hid_get_serial_number_string(device, buffer.as_mut_ptr(), 256) } {
+                0 => {
+                    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+                    let mut serial = buffer[..len].to_vec();
+                    serial.push(0);
+
+                    Some(serial).filter(|serial| serial.len() > 1)
+                }
+                _ => None,
+            };
+
+        Ok(DeviceWrapper {
+            device,
+            vendor_id,
+            product_id,
+            serial_number,
+            #[cfg(target_os = "linux")]
+            node: path.as_deref().and_then(open_node),
+            path,
+        })
+    }
+
+    /// Get the error of the last failed read, telling apart a disconnected device.
+    ///
+    /// HIDAPI does not have error codes, so the only way of detecting a disconnection is to check
+    /// the error message set by the backend, which depends on the platform.
+    fn read_error(&self) -> Error {
+        // SAFETY: The device is guaranteed to be not `null`, as the only way to get one is by
+        // calling `open`. The returned string is owned by HIDAPI and valid until the device is
+        // closed, which cannot happen while `self` is borrowed.
+        let message = unsafe { wide_to_string(ffi::hid_error(self.device)) };
+
+        match message {
+            Some(message) if is_disconnect_message(&message) => Error::Disconnected,
+            _ => Error::Read,
+        }
+    }
+}
+
+/// Open the hidraw device node at `path` read-only, if it is one.
+///
+/// Only the hidraw backend of HIDAPI lists devices by their node (e.g. `/dev/hidraw0`), so any
+/// other path (e.g. from the libusb backend) returns `None`.
+#[cfg(target_os = "linux")]
+fn open_node(path: &CStr) -> Option<File> {
+    let path = path.to_str().ok()?;
+
+    path.starts_with("/dev/hidraw")
+        .then(|| File::open(path).ok())
+        .flatten()
+}
+
+/// Check if a HIDAPI error message describes a disconnected device.
+///
+/// The known messages are, respectively, from the Linux (hidraw and libusb), macOS and Windows
+/// backends.
+fn is_disconnect_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+
+    ["no such device", "disconnected", "not connected"]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Convert a null-terminated wide string owned by HIDAPI into a [`String`].
+///
+/// Invalid characters are replaced by [`char::REPLACEMENT_CHARACTER`]. Returns `None` if `ptr` is
+/// `null`.
+///
+/// # Safety
+/// `ptr` must either be `null` or point to a valid null-terminated wide string.
+unsafe fn wide_to_string(ptr: *const wchar_t) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    let wide = std::slice::from_raw_parts(ptr, len);
+
+    // `wchar_t` is UTF-16 on Windows and UTF-32 everywhere else.
+    #[cfg(windows)]
+    let string = String::from_utf16_lossy(wide);
+    #[cfg(not(windows))]
+    let string = wide
+        .iter()
+        .map(|&c| char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+
+    Some(string)
+}
+
+impl HidDevice for DeviceWrapper {
+    /// Set the HID device mode to be either _blocking_ or _non-blocking_.
+    ///
+    /// See the [`Mode`] enum for more information.
+    ///
+    /// [`Mode`]: enum@crate::Mode
+    fn set_mode(&self, mode: Mode) -> Result<(), Error> {
+        // SAFETY: This function is safe to call since the device is guaranteed to be not `null`,
+        // as the only way to get one is by calling `open`, and we check if the pointer is valid
+        // during it. Also, the conversion of `Mode` to `c_int` is safe since the enum has
+        // well-defined values (0 for blocking and 1 for non-blocking). This function returns `-1`
+        // in case of an error and `0` otherwise.
+        match unsafe { ffi::hid_set_nonblocking(self.device, mode as c_int) } {
+            -1 => Err(Error::Mode),
+            0 => Ok(()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Read data from a HID device to `buf`.
+    ///
+    /// This function returns the number of bytes read in case of success.
+    fn read(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        // SAFETY: This function is safe to call since the device is guaranteed to be not `null`,
+        // as the only way to get one is by calling `open`, and we check if the pointer is valid
+        // during it. Also, the slice `buf` outlives the created mutable pointer. It is also
+        // guaranteed to not have buffer overflows since we pass the correct buffer length to it.
+        match unsafe { ffi::hid_read(self.device, buf.as_mut_ptr(), buf.len()) } {
+            -1 => Err(self.read_error()),
+            bytes => Ok(bytes as usize),
+        }
+    }
+
+    /// Read data from a HID device to `buf`, waiting at most `milliseconds` for it.
+    ///
+    /// A `milliseconds` value of `-1` blocks until data is available, while `0` returns
+    /// immediately. This function returns the number of bytes read in case of success, which is
+    /// `0` if the timeout elapsed without any data.
+    fn read_timeout(&self, buf: &mut [u8], milliseconds: c_int) -> Result<usize, Error> {
+        // SAFETY: This function is safe to call for the same reasons as `read`. The timeout is
+        // passed by value and any `c_int` is accepted by HIDAPI.
+        match unsafe {
+            ffi::hid_read_timeout(self.device, buf.as_mut_ptr(), buf.len(), milliseconds)
+        } {
+            -1 => Err(self.read_error()),
+            bytes => Ok(bytes as usize),
+        }
+    }
+
+    /// Write the report in `buf` to a HID device.
+    ///
+    /// This function returns the number of bytes written in case of success.
+    fn write(&self, buf: &[u8]) -> Result<usize, Error> {
+        // SAFETY: This function is safe to call since the device is guaranteed to be not `null`,
+        // as the only way to get one is by calling `open`. The slice `buf` outlives the created
+        // pointer, and HIDAPI only reads up to the supplied buffer length.
+        match unsafe { ffi::hid_write(self.device, buf.as_ptr(), buf.len()) } {
+            -1 => Err(Error::Write),
+            bytes => Ok(bytes as usize),
+        }
+    }
+
+    /// Get a feature report from a HID device to `buf`.
+    ///
+    /// This function returns the number of bytes read in case of success, including the report
+    /// ID.
+    fn get_feature_report(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        // SAFETY: This function is safe to call for the same reasons as `read`.
+        match unsafe { ffi::hid_get_feature_report(self.device, buf.as_mut_ptr(), buf.len()) } {
+            -1 => Err(Error::FeatureReport),
+            bytes => Ok(bytes as usize),
+        }
+    }
+
+    /// Get one of the strings describing the HID device.
+    ///
+    /// Strings longer than 255 characters are truncated.
+    fn get_string(&self, string: DeviceString) -> Result<String, Error> {
+        let get = match string {
+            DeviceString::Manufacturer => ffi::hid_get_manufacturer_string,
+            DeviceString::Product => ffi::hid_get_product_string,
+            DeviceString::SerialNumber => ffi::hid_get_serial_number_string,
+        };
+
+        // The last character is never written to, so the string is always null-terminated.
+        let mut buffer: [wchar_t; 256] = [0; 256];
+        // SAFETY: This function is safe to call since the device is guaranteed to be not `null`,
+        // as the only way to get one is by calling `open`. HIDAPI writes at most `maxlen`
+        // characters, which leaves the last one of the buffer untouched.
+        match unsafe { get(self.device, buffer.as_mut_ptr(), buffer.len() - 1) } {
+            // SAFETY: The buffer is null-terminated, as explained above.
+            0 => Ok(unsafe { wide_to_string(buffer.as_ptr()) }.unwrap_or_default()),
+            _ => Err(Error::DeviceString),
+        }
+    }
+
+    /// Open the same device again, using the serial number read when it was first opened.
+    ///
+    /// If the device has no serial number but was opened by its path, it is opened by the same
+    /// path instead.
+    fn reopen(&self) -> Result<Box<dyn HidDevice>, Error> {
+        let device = match (&self.serial_number, &self.path) {
+            (None, Some(path)) => {
+                // SAFETY: The path is a null-terminated string that outlives the call, and a
+                // `null` device is handled in `from_raw`.
+                let device = unsafe { ffi::hid_open_path(path.as_ptr()) };
+
+                DeviceWrapper::from_raw(
+                    device,
+                    self.vendor_id,
+                    self.product_id,
+                    Some(path.clone()),
+                )?
+            }
+            (serial_number, _) => DeviceWrapper::open_serial(
+                self.vendor_id,
+                self.product_id,
+                serial_number.as_deref(),
+            )?,
+        };
+
+        Ok(Box::new(device))
+    }
+
+    /// Get the file descriptor of the device node opened alongside the device.
+    ///
+    /// This is only available when the device was opened by the path of a hidraw device node.
+    /// The descriptor has its own queue of reports, which is never read, so it only tells when
+    /// new reports arrive: it should be waited on in edge-triggered mode.
+    #[cfg(target_os = "linux")]
+    fn raw_fd(&self) -> Option<RawFd> {
+        self.node.as_ref().map(AsRawFd::as_raw_fd)
+    }
+}
+
+impl Drop for DeviceWrapper {
+    fn drop(&mut self) {
+        // SAFETY: This is safe, since we know `self.device` is a valid device, as the only way to
+        // get one is by calling `open`, and we check if the pointer is valid during it.
+        unsafe {
+            ffi::hid_close(self.device);
+        }
+    }
+}
+
+/// List the HID devices with the supplied `vendor_id` and `product_id`, without opening them.
+///
+/// HIDAPI does not tell apart an enumeration failure from no devices being found, so both result
+/// in an empty list.
+pub(crate) fn enumerate(vendor_id: VendorID, product_id: ProductID) -> Vec<EnumeratedDevice> {
+    // SAFETY: This is safe since we only supply `unsigned short` variables to the function. It
+    // returns either a `null` pointer or a linked list that must be freed below.
+    let devices = unsafe { ffi::hid_enumerate(vendor_id.id(), product_id.id()) };
+
+    let mut list = Vec::new();
+    let mut device = devices;
+    while !device.is_null() {
+        // SAFETY: The device is not `null` and belongs to the list returned by HIDAPI, which is
+        // only freed after the loop. Its strings are either `null` or null-terminated.
+        let info = unsafe { &*device };
+        let path = if info.path.is_null() {
+            String::new()
+        } else {
+            // SAFETY: The path was checked to not be `null` above.
+            unsafe { CStr::from_ptr(info.path) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        // SAFETY: The serial number is either `null` or a null-terminated wide string.
+        let serial_number = unsafe { wide_to_string(info.serial_number) }.unwrap_or_default();
+
+        // The interface number is only valid for USB devices, being `-1` otherwise. Since the
+        // controller is either connected through USB or Bluetooth, this tells them apart without
+        // the bus type, which is only available in newer versions of HIDAPI.
+        let connection = match info.interface_number {
+            -1 => Connection::Bluetooth,
+            _ => Connection::Usb,
+        };
+
+        list.push(EnumeratedDevice {
+            path,
+            serial_number,
+            connection,
+        });
+        device = info.next;
+    }
+
+    // SAFETY: The list was returned by `hid_enumerate` and is not used after being freed. HIDAPI
+    // accepts a `null` list.
+    unsafe {
+        ffi::hid_free_enumeration(devices);
+    }
+
+    list
+}
+
+/// Finalize the HIDAPI library.
+///
+/// This function frees all of the static data associated with `HIDAPI`. It should be called when
+/// the `HIDAPI` library is not needed anymore to avoid memory leaks.
+pub(crate) fn exit() -> Result<(), Error> {
+    // SAFETY: This function is safe to call since we handle all the possible cases (`-1` for error
+    // and `0` for success).
+    match unsafe { ffi::hid_exit() } {
+        -1 => Err(Error::Exit),
+        0 => Ok(()),
+        _ => unreachable!(),
+    }
+}
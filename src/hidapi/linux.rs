@@ -0,0 +1,352 @@
+//! The pure Linux backend.
+//!
+//! This backend talks to the hidraw devices (`/dev/hidraw*`) directly, through plain `read`,
+//! `write` and `ioctl` calls, so it does not need the HIDAPI C library. The devices are found by
+//! scanning `/sys/class/hidraw`, where the `uevent` file of each device describes it.
+
+use super::{DeviceString, EnumeratedDevice, Error, HidDevice, ProductID, VendorID};
+use crate::info::Connection;
+use crate::Mode;
+
+use libc::{c_int, c_ulong};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, RawFd};
+use std::path::{Path, PathBuf};
+
+/// The directory listing every hidraw device.
+const HIDRAW_CLASS: &str = "/sys/class/hidraw";
+
+/// A hidraw device found in the system.
+struct HidrawInfo {
+    /// The name of the device node (e.g. `hidraw0`).
+    name: String,
+    vendor_id: u16,
+    product_id: u16,
+    /// The unique identifier of the device, used as its serial number. It may be empty.
+    serial_number: String,
+    connection: Connection,
+}
+
+impl HidrawInfo {
+    /// Read the information of the device `name` from its `uevent` file.
+    ///
+    /// Returns `None` if the file could not be read or does not describe a HID device.
+    fn read(name: String) -> Option<HidrawInfo> {
+        let uevent = fs::read_to_string(sysfs_path(&name).join("device/uevent")).ok()?;
+
+        // The bus types, from `linux/input.h`.
+        const BUS_USB: u32 = 0x03;
+        const BUS_BLUETOOTH: u32 = 0x05;
+
+        let mut ids = None;
+        let mut serial_number = String::new();
+        for line in uevent.lines() {
+            match line.split_once('=') {
+                // The ID is made of the bus, vendor and product IDs in hexadecimal (e.g.
+                // `0003:0000054C:00000CE6`).
+                Some(("HID_ID", id)) => {
+                    let mut parts = id.split(':').map(|part| u32::from_str_radix(part, 16).ok());
+                    let connection = match parts.next() {
+                        Some(Some(BUS_USB)) => Some(Connection::Usb),
+                        Some(Some(BUS_BLUETOOTH)) => Some(Connection::Bluetooth),
+                        _ => None,
+                    };
+                    let mut id = || u16::try_from(parts.next()??).ok();
+                    if let (Some(connection), Some(vendor_id), Some(product_id)) =
+                        (connection, id(), id())
+                    {
+                        ids = Some((connection, vendor_id, product_id));
+                    }
+                }
+                Some(("HID_UNIQ", uniq)) => serial_number = uniq.to_string(),
+                _ => {}
+            }
+        }
+
+        let (connection, vendor_id, product_id) = ids?;
+        Some(HidrawInfo {
+            name,
+            vendor_id,
+            product_id,
+            serial_number,
+            connection,
+        })
+    }
+}
+
+/// Get the sysfs directory of the hidraw device `name`.
+fn sysfs_path(name: &str) -> PathBuf {
+    Path::new(HIDRAW_CLASS).join(name)
+}
+
+/// Get the device node of the hidraw device `name`.
+fn dev_path(name: &str) -> PathBuf {
+    Path::new("/dev").join(name)
+}
+
+/// List the hidraw devices with the supplied `vendor_id` and `product_id`.
+///
+/// The devices are sorted by their number, so the same device is always listed first.
+fn hidraw_devices(vendor_id: VendorID, product_id: ProductID) -> Vec<HidrawInfo> {
+    let Ok(entries) = fs::read_dir(HIDRAW_CLASS) else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<_> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(HidrawInfo::read)
+        .filter(|info| info.vendor_id == vendor_id.id() && info.product_id == product_id.id())
+        .collect();
+    devices.sort_by(|a, b| (a.name.len(), &a.name).cmp(&(b.name.len(), &b.name)));
+
+    devices
+}
+
+/// Build the `HIDIOCGFEATURE` ioctl request for a buffer of `len` bytes.
+///
+/// The request follows the generic ioctl encoding, used by most architectures (e.g. x86 and ARM):
+/// the direction (read and write) in the top 2 bits, followed by the 14 bits of the buffer length,
+/// the type (`H`) and the number (`0x07`).
+const fn hidiocgfeature(len: usize) -> c_ulong {
+    const READ_WRITE: c_ulong = 0b11;
+
+    READ_WRITE << 30 | (len as c_ulong & 0x3FFF) << 16 | (b'H' as c_ulong) << 8 | 0x07
+}
+
+/// A wrapper for a hidraw device.
+pub(crate) struct DeviceWrapper {
+    file: File,
+    /// The name of the device node, used to read its attributes.
+    name: String,
+    vendor_id: VendorID,
+    product_id: ProductID,
+    /// The serial number of the device, used to open the same device again.
+    serial_number: String,
+}
+
+impl DeviceWrapper {
+    /// Try to open a HID device.
+    ///
+    /// This function can fail. The most common reason is if a device with the supplied `vendor_id`
+    /// and `product_id` was not found, or if the user has no permission to open it.
+    pub(crate) fn open(vendor_id: VendorID, product_id: ProductID) -> Result<Self, Error> {
+        DeviceWrapper::open_serial(vendor_id, product_id, None)
+    }
+
+    /// Try to open a HID device, optionally with a specific `serial_number`.
+    ///
+    /// If `serial_number` is `None`, the first device with the supplied `vendor_id` and
+    /// `product_id` is opened.
+    fn open_serial(
+        vendor_id: VendorID,
+        product_id: ProductID,
+        serial_number: Option<&str>,
+    ) -> Result<Self, Error> {
+        let info = hidraw_devices(vendor_id, product_id)
+            .into_iter()
+            .find(|info| serial_number.is_none_or(|serial| info.serial_number == serial))
+            .ok_or(Error::Open)?;
+
+        DeviceWrapper::open_info(vendor_id, product_id, info)
+    }
+
+    /// Try to open a HID device by its `path` (e.g. `/dev/hidraw0`), as listed by [`enumerate`].
+    ///
+    /// Only devices with the supplied `vendor_id` and `product_id` can be opened.
+    ///
+    /// [`enumerate`]: fn@crate::hidapi::enumerate
+    pub(crate) fn open_path(
+        vendor_id: VendorID,
+        product_id: ProductID,
+        path: &str,
+    ) -> Result<Self, Error> {
+        let info = hidraw_devices(vendor_id, product_id)
+            .into_iter()
+            .find(|info| dev_path(&info.name) == Path::new(path))
+            .ok_or(Error::Open)?;
+
+        DeviceWrapper::open_info(vendor_id, product_id, info)
+    }
+
+    /// Open the device node of a hidraw device found while enumerating.
+    fn open_info(
+        vendor_id: VendorID,
+        product_id: ProductID,
+        info: HidrawInfo,
+    ) -> Result<Self, Error> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(dev_path(&info.name))
+            .map_err(|_| Error::Open)?;
+
+        Ok(DeviceWrapper {
+            file,
+            name: info.name,
+            vendor_id,
+            product_id,
+            serial_number: info.serial_number,
+        })
+    }
+
+    /// Get the error of a failed read, telling apart a disconnected device.
+    fn read_error(error: io::Error) -> Error {
+        match error.raw_os_error() {
+            Some(libc::ENODEV | libc::EIO) => Error::Disconnected,
+            _ => Error::Read,
+        }
+    }
+
+    /// Read an attribute of the USB device the hidraw device belongs to.
+    ///
+    /// Controllers connected through Bluetooth have no USB device, so their attributes are never
+    /// found.
+    fn usb_attribute(&self, attribute: &str) -> Option<String> {
+        // The `device` is the HID device, whose parent is the USB interface, whose parent is the
+        // USB device.
+        let path = sysfs_path(&self.name).join("device/../..").join(attribute);
+
+        fs::read_to_string(path)
+            .ok()
+            .map(|value| value.trim_end().to_string())
+    }
+}
+
+impl HidDevice for DeviceWrapper {
+    /// Set the HID device mode to be either _blocking_ or _non-blocking_.
+    ///
+    /// See the [`Mode`] enum for more information.
+    ///
+    /// [`Mode`]: enum@crate::Mode
+    fn set_mode(&self, mode: Mode) -> Result<(), Error> {
+        let fd = self.file.as_raw_fd();
+
+        // SAFETY: The file descriptor is owned by `self.file`, so it is valid while `self` is
+        // borrowed. `F_GETFL` takes no argument and returns `-1` in case of an error.
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+        if flags == -1 {
+            return Err(Error::Mode);
+        }
+
+        let flags = match mode {
+            Mode::Blocking => flags & !libc::O_NONBLOCK,
+            Mode::NonBlocking => flags | libc::O_NONBLOCK,
+        };
+        // SAFETY: The file descriptor is valid, as explained above, and `F_SETFL` takes the new
+        // flags by value, returning `-1` in case of an error.
+        match unsafe { libc::fcntl(fd, libc::F_SETFL, flags) } {
+            -1 => Err(Error::Mode),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read data from a HID device to `buf`.
+    ///
+    /// This function returns the number of bytes read in case of success, which is `0` if the
+    /// device is in _non-blocking_ mode and no data is available.
+    fn read(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        match (&self.file).read(buf) {
+            Ok(bytes) => Ok(bytes),
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => Ok(0),
+            Err(error) => Err(DeviceWrapper::read_error(error)),
+        }
+    }
+
+    /// Read data from a HID device to `buf`, waiting at most `milliseconds` for it.
+    ///
+    /// A `milliseconds` value of `-1` blocks until data is available, while `0` returns
+    /// immediately. This function returns the number of bytes read in case of success, which is
+    /// `0` if the timeout elapsed without any data.
+    fn read_timeout(&self, buf: &mut [u8], milliseconds: c_int) -> Result<usize, Error> {
+        let mut fd = libc::pollfd {
+            fd: self.file.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        // SAFETY: `fd` is a single valid `pollfd`, which outlives the call, and the file
+        // descriptor is owned by `self.file`. This function returns `-1` in case of an error and
+        // the number of ready descriptors otherwise.
+        match unsafe { libc::poll(&mut fd, 1, milliseconds) } {
+            -1 => Err(Error::Read),
+            0 => Ok(0),
+            _ if fd.revents & (libc::POLLERR | libc::POLLHUP | libc::POLLNVAL) != 0 => {
+                Err(Error::Disconnected)
+            }
+            _ => self.read(buf),
+        }
+    }
+
+    /// Write the report in `buf` to a HID device.
+    ///
+    /// This function returns the number of bytes written in case of success.
+    fn write(&self, buf: &[u8]) -> Result<usize, Error> {
+        (&self.file).write(buf).map_err(|_| Error::Write)
+    }
+
+    /// Get a feature report from a HID device to `buf`.
+    ///
+    /// This function returns the number of bytes read in case of success, including the report
+    /// ID.
+    fn get_feature_report(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        // SAFETY: The file descriptor is owned by `self.file`, and the length encoded in the
+        // request is the length of `buf`, so the kernel does not write past its end. This
+        // function returns `-1` in case of an error and the number of bytes read otherwise.
+        match unsafe {
+            libc::ioctl(
+                self.file.as_raw_fd(),
+                hidiocgfeature(buf.len()) as _,
+                buf.as_mut_ptr(),
+            )
+        } {
+            -1 => Err(Error::FeatureReport),
+            bytes => Ok(bytes as usize),
+        }
+    }
+
+    /// Get one of the strings describing the HID device.
+    ///
+    /// The manufacturer and product strings are read from the USB device, so they are not
+    /// available through Bluetooth.
+    fn get_string(&self, string: DeviceString) -> Result<String, Error> {
+        match string {
+            DeviceString::Manufacturer => self.usb_attribute("manufacturer"),
+            DeviceString::Product => self.usb_attribute("product"),
+            DeviceString::SerialNumber => Some(self.serial_number.clone()),
+        }
+        .ok_or(Error::DeviceString)
+    }
+
+    /// Open the same device again, using the serial number read when it was first opened.
+    fn reopen(&self) -> Result<Box<dyn HidDevice>, Error> {
+        let serial_number = Some(self.serial_number.as_str()).filter(|serial| !serial.is_empty());
+        let device = DeviceWrapper::open_serial(self.vendor_id, self.product_id, serial_number)?;
+
+        Ok(Box::new(device))
+    }
+
+    /// Get the hidraw file descriptor of the device.
+    fn raw_fd(&self) -> Option<RawFd> {
+        Some(self.file.as_raw_fd())
+    }
+}
+
+/// List the HID devices with the supplied `vendor_id` and `product_id`, without opening them.
+pub(crate) fn enumerate(vendor_id: VendorID, product_id: ProductID) -> Vec<EnumeratedDevice> {
+    hidraw_devices(vendor_id, product_id)
+        .into_iter()
+        .map(|info| EnumeratedDevice {
+            path: dev_path(&info.name).to_string_lossy().into_owned(),
+            serial_number: info.serial_number,
+            connection: info.connection,
+        })
+        .collect()
+}
+
+/// Finalize the backend.
+///
+/// This backend has no static data, so there is nothing to free.
+pub(crate) fn exit() -> Result<(), Error> {
+    Ok(())
+}
@@ -0,0 +1,221 @@
+//! The inertial measurement unit (IMU) module.
+//!
+//! This module estimates the orientation of the controller by combining the readings of its
+//! gyroscope and accelerometer. The estimation is simple enough to not need any external
+//! dependency, while still being good enough for motion controls.
+
+use crate::DualSenseState;
+
+/// A quaternion, stored as `[w, x, y, z]`.
+type Quaternion = [f32; 4];
+
+/// The identity quaternion (no rotation).
+const IDENTITY: Quaternion = [1.0, 0.0, 0.0, 0.0];
+
+/// The default filter coefficient.
+const DEFAULT_COEFFICIENT: f32 = 0.98;
+
+/// An orientation estimator based on a complementary filter.
+///
+/// The gyroscope is precise in the short term, but its integration drifts over time. On the other
+/// hand, the accelerometer measures the direction of gravity, which is noisy but does not drift.
+/// The complementary filter integrates the gyroscope and then nudges the result towards the
+/// direction of gravity measured by the accelerometer, getting the best of both worlds.
+///
+/// The orientation is returned as a unit quaternion `[w, x, y, z]`, rotating vectors from the
+/// controller axes (see [`AccelerationState`]) to the world axes, where `Y` points up. Since
+/// gravity gives no information about the rotation around the vertical axis, the yaw still drifts
+/// slowly over time.
+///
+/// ```rust
+/// use duplosentido::{imu::Imu, DualSenseState};
+///
+/// // A controller at rest on a table.
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[8] = 0x08;
+/// report[24..=25].copy_from_slice(&8192_i16.to_le_bytes());
+/// let state = DualSenseState::from_raw(report).unwrap();
+///
+/// let mut imu = Imu::new();
+/// let [w, x, y, z] = imu.update(&state);
+///
+/// assert!((w - 1.0).abs() < 1e-6);
+/// assert!(x.abs() < 1e-6 && y.abs() < 1e-6 && z.abs() < 1e-6);
+/// ```
+///
+/// [`AccelerationState`]: struct@crate::mappings::AccelerationState
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Imu {
+    orientation: Quaternion,
+    coefficient: f32,
+    previous: Option<DualSenseState>,
+}
+
+impl Imu {
+    /// Create an orientation estimator with the default filter coefficient (`0.98`).
+    pub fn new() -> Self {
+        Imu::with_coefficient(DEFAULT_COEFFICIENT)
+    }
+
+    /// Create an orientation estimator with the given filter coefficient.
+    ///
+    /// See [`set_coefficient`] for more information about the coefficient.
+    ///
+    /// [`set_coefficient`]: fn@Self::set_coefficient
+    pub fn with_coefficient(coefficient: f32) -> Self {
+        let mut imu = Imu {
+            orientation: IDENTITY,
+            coefficient: DEFAULT_COEFFICIENT,
+            previous: None,
+        };
+        imu.set_coefficient(coefficient);
+
+        imu
+    }
+
+    /// Set the filter coefficient.
+    ///
+    /// The coefficient ranges from `0.0` to `1.0` (values outside of it are clamped) and indicates
+    /// how much the gyroscope is trusted over the accelerometer on every update. Higher values
+    /// give smoother results but take longer to correct the drift, while lower values correct the
+    /// drift faster but are more sensitive to the controller being shaken.
+    pub fn set_coefficient(&mut self, coefficient: f32) {
+        self.coefficient = coefficient.clamp(0.0, 1.0);
+    }
+
+    /// Get the filter coefficient.
+    pub fn coefficient(&self) -> f32 {
+        self.coefficient
+    }
+
+    /// Get the current orientation estimate as `[w, x, y, z]`.
+    pub fn orientation(&self) -> [f32; 4] {
+        self.orientation
+    }
+
+    /// Reset the estimator, as if it was just created.
+    pub fn reset(&mut self) {
+        self.orientation = IDENTITY;
+        self.previous = None;
+    }
+
+    /// Feed a new controller state to the estimator, returning the new orientation estimate.
+    ///
+    /// The states should be fed in the order they were read from the controller, since the time
+    /// between them is computed from their [`sensor_delta`]. The first state only initializes the
+    /// orientation from the direction of gravity.
+    ///
+    /// [`sensor_delta`]: fn@crate::DualSenseState::sensor_delta
+    pub fn update(&mut self, state: &DualSenseState) -> [f32; 4] {
+        let acceleration = state.acceleration();
+        let gravity = [acceleration.x_g(), acceleration.y_g(), acceleration.z_g()];
+
+        let Some(previous) = self.previous.replace(*state) else {
+            if let Some(gravity) = normalize(gravity) {
+                self.orientation = between(gravity, [0.0, 1.0, 0.0]);
+            }
+
+            return self.orientation;
+        };
+
+        // Integrate the gyroscope: q' = q + 0.5 * q * (0, w) * dt.
+        let dt = state.sensor_delta(&previous).as_secs_f32();
+        let gyroscope = state.gyroscope();
+        let [x, y, z] = [gyroscope.x_dps(), gyroscope.y_dps(), gyroscope.z_dps()]
+            .map(|dps| dps.to_radians() * dt * 0.5);
+        let delta = multiply(self.orientation, [0.0, x, y, z]);
+        let orientation = std::array::from_fn(|i| self.orientation[i] + delta[i]);
+        let mut orientation = normalize(orientation).unwrap_or(IDENTITY);
+
+        // Nudge the measured gravity towards the expected one, in the controller axes.
+        if let Some(gravity) = normalize(gravity) {
+            let expected = rotate(conjugate(orientation), [0.0, 1.0, 0.0]);
+            let correction = slerp_identity(between(gravity, expected), 1.0 - self.coefficient);
+            orientation = normalize(multiply(orientation, correction)).unwrap_or(IDENTITY);
+        }
+
+        self.orientation = orientation;
+        self.orientation
+    }
+}
+
+impl Default for Imu {
+    fn default() -> Self {
+        Imu::new()
+    }
+}
+
+/// Multiply two quaternions.
+fn multiply(a: Quaternion, b: Quaternion) -> Quaternion {
+    let [aw, ax, ay, az] = a;
+    let [bw, bx, by, bz] = b;
+
+    [
+        aw * bw - ax * bx - ay * by - az * bz,
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+    ]
+}
+
+/// Get the conjugate (inverse, for unit quaternions) of a quaternion.
+fn conjugate([w, x, y, z]: Quaternion) -> Quaternion {
+    [w, -x, -y, -z]
+}
+
+/// Rotate a vector by a unit quaternion.
+fn rotate(q: Quaternion, [x, y, z]: [f32; 3]) -> [f32; 3] {
+    let [_, x, y, z] = multiply(multiply(q, [0.0, x, y, z]), conjugate(q));
+
+    [x, y, z]
+}
+
+/// Normalize a vector (or quaternion), returning `None` if its length is zero.
+fn normalize<const N: usize>(v: [f32; N]) -> Option<[f32; N]> {
+    let length = v.iter().map(|c| c * c).sum::<f32>().sqrt();
+
+    (length > f32::EPSILON).then(|| v.map(|c| c / length))
+}
+
+/// Get the shortest rotation from the unit vector `from` to the unit vector `to`.
+fn between(from: [f32; 3], to: [f32; 3]) -> Quaternion {
+    let [fx, fy, fz] = from;
+    let [tx, ty, tz] = to;
+    let dot = fx * tx + fy * ty + fz * tz;
+
+    // Opposite vectors have infinite shortest rotations, so pick any perpendicular axis.
+    if dot < -1.0 + 1e-6 {
+        let axis = if fx.abs() < 0.9 {
+            [0.0, fz, -fy]
+        } else {
+            [-fz, 0.0, fx]
+        };
+        let [x, y, z] = normalize(axis).unwrap_or([0.0, 0.0, 1.0]);
+
+        return [0.0, x, y, z];
+    }
+
+    let q = [
+        1.0 + dot,
+        fy * tz - fz * ty,
+        fz * tx - fx * tz,
+        fx * ty - fy * tx,
+    ];
+
+    normalize(q).unwrap_or(IDENTITY)
+}
+
+/// Scale the rotation of a unit quaternion by `t` (from `0.0`, no rotation, to `1.0`).
+fn slerp_identity(q: Quaternion, t: f32) -> Quaternion {
+    let [w, x, y, z] = q;
+    let angle = w.clamp(-1.0, 1.0).acos();
+    let sin = angle.sin();
+
+    if sin.abs() < f32::EPSILON {
+        return IDENTITY;
+    }
+
+    let scale = (angle * t).sin() / sin;
+    [(angle * t).cos(), x * scale, y * scale, z * scale]
+}
@@ -0,0 +1,432 @@
+//! The controller information module.
+//!
+//! This module contains the information that can be read directly from the controller, such as
+//! its firmware version. Unlike the controller state, this information does not change while the
+//! controller is being used, so it only needs to be read once.
+
+use crate::mappings::{ACCELEROMETER_RESOLUTION, GYROSCOPE_RESOLUTION};
+use crate::{Error, KnownController, Result};
+
+/// The firmware information of a DualSense controller.
+///
+/// This information is read from the feature report `0x20` with [`firmware_info`]. The meaning of
+/// the version fields is not officially documented, so they are exposed as they are sent by the
+/// controller:
+///
+/// ```rust
+/// use duplosentido::info::FirmwareInfo;
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x20;
+/// report[1..12].copy_from_slice(b"Jun 16 2021");
+/// report[12..20].copy_from_slice(b"09:31:45");
+/// report[24..28].copy_from_slice(&0x0000_0515_u32.to_le_bytes());
+/// report[28..32].copy_from_slice(&0x0110_0003_u32.to_le_bytes());
+/// report[44..46].copy_from_slice(&0x0430_u16.to_le_bytes());
+///
+/// let info = FirmwareInfo::from_raw(&report).unwrap();
+///
+/// assert_eq!(info.build_date(), "Jun 16 2021");
+/// assert_eq!(info.build_time(), "09:31:45");
+/// assert_eq!(info.hardware_version(), 0x0000_0515);
+/// assert_eq!(info.firmware_version(), 0x0110_0003);
+/// assert_eq!(info.update_version(), 0x0430);
+/// ```
+///
+/// [`firmware_info`]: fn@crate::DualSense::firmware_info
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareInfo {
+    build_date: String,
+    build_time: String,
+    hardware_version: u32,
+    firmware_version: u32,
+    update_version: u16,
+}
+
+impl FirmwareInfo {
+    /// The ID of the feature report containing the firmware information.
+    pub(crate) const REPORT_ID: u8 = 0x20;
+
+    /// The minimum length of the report needed to read all of the fields.
+    const REPORT_LENGTH: usize = 46;
+
+    /// Create the firmware information from a raw feature report.
+    ///
+    /// # Errors
+    /// If the report is not a firmware report (ID `0x20`) or is too short,
+    /// [`Error::UnsupportedReport`] is returned.
+    ///
+    /// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+    pub fn from_raw(report: &[u8]) -> Result<FirmwareInfo> {
+        if report.len() < FirmwareInfo::REPORT_LENGTH || report[0] != FirmwareInfo::REPORT_ID {
+            return Err(Error::UnsupportedReport {
+                id: report.first().copied().unwrap_or(0),
+                len: report.len(),
+            });
+        }
+
+        Ok(FirmwareInfo {
+            build_date: ascii(&report[1..12]),
+            build_time: ascii(&report[12..20]),
+            hardware_version: u32::from_le_bytes([report[24], report[25], report[26], report[27]]),
+            firmware_version: u32::from_le_bytes([report[28], report[29], report[30], report[31]]),
+            update_version: u16::from_le_bytes([report[44], report[45]]),
+        })
+    }
+
+    /// Get the firmware build date (e.g. `Jun 16 2021`).
+    pub fn build_date(&self) -> &str {
+        &self.build_date
+    }
+
+    /// Get the firmware build time (e.g. `09:31:45`).
+    pub fn build_time(&self) -> &str {
+        &self.build_time
+    }
+
+    /// Get the hardware version.
+    pub fn hardware_version(&self) -> u32 {
+        self.hardware_version
+    }
+
+    /// Get the firmware version.
+    pub fn firmware_version(&self) -> u32 {
+        self.firmware_version
+    }
+
+    /// Get the firmware update version.
+    ///
+    /// This is the version shown by the console, where `0x0430` means version `04.30`.
+    pub fn update_version(&self) -> u16 {
+        self.update_version
+    }
+}
+
+/// The calibration of a single motion sensor axis.
+///
+/// A raw reading is converted by removing its bias and then scaling it by `numerator /
+/// denominator`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct AxisCalibration {
+    bias: i16,
+    numerator: i32,
+    denominator: i32,
+}
+
+impl AxisCalibration {
+    /// Create an axis calibration, falling back to the nominal `resolution` if the calibration
+    /// data is invalid (which would otherwise lead to a division by zero).
+    fn new(bias: i16, numerator: i32, denominator: i32, resolution: i32) -> Self {
+        if denominator == 0 {
+            return AxisCalibration {
+                bias: 0,
+                numerator: 1,
+                denominator: resolution,
+            };
+        }
+
+        AxisCalibration {
+            bias,
+            numerator,
+            denominator,
+        }
+    }
+
+    fn apply(&self, raw: i16) -> f32 {
+        (raw as i32 - self.bias as i32) as f32 * self.numerator as f32 / self.denominator as f32
+    }
+}
+
+/// The motion sensors calibration of a DualSense controller.
+///
+/// Every controller has slightly different motion sensors, so the raw readings of the gyroscope
+/// and the accelerometer have to be calibrated to be accurate. The calibration is stored in the
+/// controller itself, and can be read from the feature report `0x05` with [`read_calibration`].
+///
+/// The calibration is applied with [`AngularVelocityState::calibrated_dps`] and
+/// [`AccelerationState::calibrated_g`]:
+///
+/// ```rust
+/// use duplosentido::info::Calibration;
+/// use duplosentido::DualSenseState;
+///
+/// let mut calibration = [0_u8; 41];
+/// calibration[0] = 0x05;
+/// // Gyroscope bias, then the readings at +/- the reference speed for each axis.
+/// let gyro = [10, 0, 0, 1034, -1014, 1024, -1024, 1024, -1024];
+/// // Gyroscope reference speed (+/-), then the accelerometer readings at +/- 1 g for each axis.
+/// let rest = [500, 500, 8292, -8092, 8192, -8192, 8192, -8192];
+/// for (i, value) in gyro.iter().chain(rest.iter()).enumerate() {
+///     calibration[1 + 2 * i..3 + 2 * i].copy_from_slice(&(*value as i16).to_le_bytes());
+/// }
+/// let calibration = Calibration::from_raw(&calibration).unwrap();
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[8] = 0x08;
+/// report[16..=17].copy_from_slice(&1034_i16.to_le_bytes());
+/// report[22..=23].copy_from_slice(&8292_i16.to_le_bytes());
+/// let state = DualSenseState::from_raw(report).unwrap();
+///
+/// assert_eq!(state.gyroscope().calibrated_dps(&calibration), [500.0, 0.0, 0.0]);
+/// assert_eq!(state.acceleration().calibrated_g(&calibration), [1.0, 0.0, 0.0]);
+/// ```
+///
+/// [`read_calibration`]: fn@crate::DualSense::read_calibration
+/// [`AngularVelocityState::calibrated_dps`]: fn@crate::mappings::AngularVelocityState::calibrated_dps
+/// [`AccelerationState::calibrated_g`]: fn@crate::mappings::AccelerationState::calibrated_g
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Calibration {
+    /// The gyroscope calibration, in `X`, `Y` and `Z` order.
+    gyroscope: [AxisCalibration; 3],
+    /// The accelerometer calibration, in `X`, `Y` and `Z` order.
+    accelerometer: [AxisCalibration; 3],
+}
+
+impl Calibration {
+    /// The ID of the feature report containing the calibration.
+    pub(crate) const REPORT_ID: u8 = 0x05;
+
+    /// The length of the calibration report.
+    const REPORT_LENGTH: usize = 41;
+
+    /// Create the calibration from a raw feature report.
+    ///
+    /// # Errors
+    /// If the report is not a calibration report (ID `0x05`) or is too short,
+    /// [`Error::UnsupportedReport`] is returned.
+    ///
+    /// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+    pub fn from_raw(report: &[u8]) -> Result<Calibration> {
+        if report.len() < Calibration::REPORT_LENGTH || report[0] != Calibration::REPORT_ID {
+            return Err(Error::UnsupportedReport {
+                id: report.first().copied().unwrap_or(0),
+                len: report.len(),
+            });
+        }
+
+        let field =
+            |index: usize| i16::from_le_bytes([report[1 + 2 * index], report[2 + 2 * index]]);
+
+        // The report stores the gyroscope axes as pitch, yaw and roll, which are read from the
+        // input report bytes of the `X`, `Z` and `Y` axes, respectively.
+        let (pitch_bias, yaw_bias, roll_bias) = (field(0), field(1), field(2));
+        let (pitch_plus, pitch_minus) = (field(3), field(4));
+        let (yaw_plus, yaw_minus) = (field(5), field(6));
+        let (roll_plus, roll_minus) = (field(7), field(8));
+        let speed = field(9) as i32 + field(10) as i32;
+
+        let gyro = |bias: i16, plus: i16, minus: i16| {
+            let denominator =
+                (plus as i32 - bias as i32).abs() + (minus as i32 - bias as i32).abs();
+
+            AxisCalibration::new(bias, speed, denominator, GYROSCOPE_RESOLUTION as i32)
+        };
+
+        // The accelerometer readings at +1 g and -1 g are stored for each axis.
+        let accel = |plus: i16, minus: i16| {
+            let range = plus as i32 - minus as i32;
+            let bias = (plus as i32 - range / 2) as i16;
+
+            AxisCalibration::new(bias, 2, range, ACCELEROMETER_RESOLUTION as i32)
+        };
+
+        Ok(Calibration {
+            gyroscope: [
+                gyro(pitch_bias, pitch_plus, pitch_minus),
+                gyro(roll_bias, roll_plus, roll_minus),
+                gyro(yaw_bias, yaw_plus, yaw_minus),
+            ],
+            accelerometer: [
+                accel(field(11), field(12)),
+                accel(field(13), field(14)),
+                accel(field(15), field(16)),
+            ],
+        })
+    }
+
+    /// Convert raw gyroscope readings (`X`, `Y` and `Z`) to degrees per second.
+    pub(crate) fn gyroscope(&self, raw: [i16; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|i| self.gyroscope[i].apply(raw[i]))
+    }
+
+    /// Convert raw accelerometer readings (`X`, `Y` and `Z`) to units of g.
+    pub(crate) fn accelerometer(&self, raw: [i16; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|i| self.accelerometer[i].apply(raw[i]))
+    }
+}
+
+/// The pairing information of a DualSense controller.
+///
+/// This information is read from the feature report `0x09` with [`pairing_info`]. It contains the
+/// Bluetooth address of the controller, which can be used to identify the same physical controller
+/// across reboots, and the address of the host it is paired to.
+///
+/// The addresses are in the usual display order, so the first byte is the most significant one:
+///
+/// ```rust
+/// use duplosentido::info::PairingInfo;
+///
+/// let mut report = [0_u8; 20];
+/// report[0] = 0x09;
+/// // The addresses are sent least significant byte first.
+/// report[1..7].copy_from_slice(&[0x66, 0x55, 0x44, 0x33, 0x22, 0x11]);
+/// report[10..16].copy_from_slice(&[0xFF, 0xEE, 0xDD, 0xCC, 0xBB, 0xAA]);
+///
+/// let info = PairingInfo::from_raw(&report).unwrap();
+///
+/// assert_eq!(info.address(), [0x11, 0x22, 0x33, 0x44, 0x55, 0x66]);
+/// assert_eq!(info.host_address(), [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+/// ```
+///
+/// [`pairing_info`]: fn@crate::DualSense::pairing_info
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairingInfo {
+    address: [u8; 6],
+    host_address: [u8; 6],
+}
+
+impl PairingInfo {
+    /// The ID of the feature report containing the pairing information.
+    pub(crate) const REPORT_ID: u8 = 0x09;
+
+    /// The length of the pairing report.
+    const REPORT_LENGTH: usize = 20;
+
+    /// Create the pairing information from a raw feature report.
+    ///
+    /// # Errors
+    /// If the report is not a pairing report (ID `0x09`) or is too short,
+    /// [`Error::UnsupportedReport`] is returned.
+    ///
+    /// [`Error::UnsupportedReport`]: crate::Error::UnsupportedReport
+    pub fn from_raw(report: &[u8]) -> Result<PairingInfo> {
+        if report.len() < PairingInfo::REPORT_LENGTH || report[0] != PairingInfo::REPORT_ID {
+            return Err(Error::UnsupportedReport {
+                id: report.first().copied().unwrap_or(0),
+                len: report.len(),
+            });
+        }
+
+        let mut address: [u8; 6] = report[1..7].try_into().unwrap();
+        let mut host_address: [u8; 6] = report[10..16].try_into().unwrap();
+        address.reverse();
+        host_address.reverse();
+
+        Ok(PairingInfo {
+            address,
+            host_address,
+        })
+    }
+
+    /// Get the Bluetooth address of the controller.
+    pub fn address(&self) -> [u8; 6] {
+        self.address
+    }
+
+    /// Get the Bluetooth address of the host the controller is paired to.
+    ///
+    /// If the controller was never paired, the address is all zeros.
+    pub fn host_address(&self) -> [u8; 6] {
+        self.host_address
+    }
+}
+
+/// The strings describing a DualSense controller.
+///
+/// These strings are read from the USB descriptors of the controller with [`device_info`], and
+/// are useful to show the controller to the user (e.g. `Sony Interactive Entertainment DualSense
+/// Wireless Controller`).
+///
+/// [`device_info`]: fn@crate::DualSense::device_info
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceStrings {
+    pub(crate) manufacturer: String,
+    pub(crate) product: String,
+    pub(crate) serial_number: String,
+}
+
+impl DeviceStrings {
+    /// Get the manufacturer of the controller (e.g. `Sony Interactive Entertainment`).
+    pub fn manufacturer(&self) -> &str {
+        &self.manufacturer
+    }
+
+    /// Get the product name of the controller (e.g. `DualSense Wireless Controller`).
+    pub fn product(&self) -> &str {
+        &self.product
+    }
+
+    /// Get the serial number of the controller.
+    ///
+    /// Some controllers do not report a serial number, in which case it is empty.
+    pub fn serial_number(&self) -> &str {
+        &self.serial_number
+    }
+}
+
+/// How a controller is connected to the host.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connection {
+    /// The controller is connected through a USB cable.
+    Usb,
+    /// The controller is connected wirelessly, through Bluetooth.
+    Bluetooth,
+}
+
+/// A connected DualSense controller, found without binding to it.
+///
+/// The controllers are listed with [`list`], and any of them can then be binded to with
+/// [`bind_path`]. The path is assigned by the platform (e.g. `/dev/hidraw0` on Linux), and stays
+/// the same while the controller is connected, which makes it a more robust way of selecting a
+/// controller than its serial number, that may be missing.
+///
+/// [`list`]: fn@crate::DualSense::list
+/// [`bind_path`]: fn@crate::DualSense::bind_path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub(crate) path: String,
+    pub(crate) kind: KnownController,
+    pub(crate) serial_number: String,
+    pub(crate) connection: Connection,
+}
+
+impl DeviceInfo {
+    /// Get the platform-specific path of the controller.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the model of the controller.
+    pub fn kind(&self) -> KnownController {
+        self.kind
+    }
+
+    /// Get the serial number of the controller.
+    ///
+    /// Some controllers do not report a serial number, in which case it is empty.
+    pub fn serial_number(&self) -> &str {
+        &self.serial_number
+    }
+
+    /// Get how the controller is connected.
+    ///
+    /// A controller connected through USB while also paired through Bluetooth is listed once for
+    /// each connection.
+    pub fn connection(&self) -> Connection {
+        self.connection
+    }
+}
+
+/// Convert a fixed-size ASCII field into a [`String`], ignoring any trailing null bytes.
+fn ascii(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches('\0')
+        .to_string()
+}
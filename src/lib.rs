@@ -37,27 +37,81 @@
 //! }
 //! ```
 //!
+//! # Features
+//! This crate has no optional features enabled by default. The available features are:
+//! - `serde`: implements [`Serialize`] and [`Deserialize`] for [`DualSenseState`] and all of the
+//!   states in [`mappings`], making it possible to save or send them over the network.
+//! - `mock`: enables the `mock` module, with a fake device that can be binded with
+//!   `DualSense::mock` to test code without a controller.
+//! - `tokio`: adds `DualSense::update_async`, which updates the controller without blocking the
+//!   [`tokio`] executor.
+//! - `bevy`: enables the `bevy` module, with a plugin that pumps the controller state into the
+//!   [Bevy] ECS every frame.
+//! - `gamepad`: enables the `gamepad` module, with a generic `Gamepad` trait and an adapter that
+//!   implements it for the controller, so it can be used alongside other gamepad backends.
+//! - `linux-hidraw`: on Linux, talks to the controller through the hidraw devices
+//!   (`/dev/hidraw*`) directly, instead of linking against the HIDAPI library.
+//!
+//! ```rust
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use duplosentido::mappings::{Button, ButtonState};
+//!
+//! let json = serde_json::to_string(&(Button::Cross, ButtonState::Pressed)).unwrap();
+//! let (button, state): (Button, ButtonState) = serde_json::from_str(&json).unwrap();
+//!
+//! assert_eq!(button, Button::Cross);
+//! assert_eq!(state, ButtonState::Pressed);
+//! # }
+//! ```
+//!
 //! # Roadmap
 //! This crate is still a major work in progress. Below you can see the "roadmap" for this crate,
 //! in no particular order:
 //! - [x] Linux support.
-//! - [ ] Windows and macOS support.
+//! - [x] Windows and macOS support.
 //! - [x] USB connection support.
 //! - [ ] Bluetooth connection support.
-//! - [ ] Adaptive trigger support.
-//! - [ ] Vibration support.
+//! - [x] Adaptive trigger support.
+//! - [x] Vibration support.
 //! - [ ] No dependencies (maybe?).
 //!
 //! [`DualSense`]: struct@crate::DualSense
 //! [`update`]: fn@crate::DualSense::update
 //! [`set_mode`]: fn@crate::DualSense::set_mode
 //! [`state`]: fn@crate::DualSense::state
+//! [`DualSenseState`]: struct@crate::DualSenseState
+//! [`mappings`]: mod@crate::mappings
+//! [`Serialize`]: https://docs.rs/serde/latest/serde/trait.Serialize.html
+//! [`Deserialize`]: https://docs.rs/serde/latest/serde/trait.Deserialize.html
+//! [`tokio`]: https://docs.rs/tokio/latest/tokio/
+//! [Bevy]: https://bevyengine.org
 
 #![warn(missing_docs)]
 
 pub(crate) mod hidapi;
 
+#[cfg(feature = "bevy")]
+pub mod bevy;
+
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+
+pub mod gesture;
+pub mod imu;
+pub mod info;
 pub mod mappings;
+pub mod output;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+pub mod poller;
+pub mod recording;
+pub mod tracker;
 
 mod dualsense;
-pub use crate::dualsense::{DualSense, DualSenseState, Error, Mode, Result};
+pub use crate::dualsense::{
+    ConnectionPreference, DualSense, DualSenseState, Error, KnownController, Mode, Result,
+    StateDiff,
+};
@@ -4,12 +4,18 @@
 //! DualSense controller. Buttons and parts are represented as states, which are snapshots of a
 //! controller components as it was during its last update.
 
+pub mod bits;
 pub(crate) mod group;
+pub use group::EdgeButtons;
 
 mod state;
+pub(crate) use state::{
+    apply_deadzone, Axis, BackTriggerStop, StickCoordinates, ACCELEROMETER_RESOLUTION,
+    GYROSCOPE_RESOLUTION,
+};
 pub use state::{
     AccelerationState, AngularVelocityState, BackTriggerEffect, BackTriggerState,
-    BackTriggerStatus, ButtonState, DPadDirection, MicrophoneState, MutedState, PluggedState,
-    PowerState, StickState, TemperatureState, TouchPadState, USBState,
+    BackTriggerStatus, Button, ButtonState, DPadDirection, FingerData, MicrophoneState, MutedState,
+    PluggedState, PowerState, StickCalibration, StickState, TemperatureState, TouchPadState,
+    TriggerCalibration, USBState, STICK_CENTER, TOUCHPAD_HEIGHT, TOUCHPAD_WIDTH,
 };
-pub(crate) use state::{Axis, BackTriggerStop, FingerData, StickCoordinates};
@@ -0,0 +1,49 @@
+//! The bit layout of the packed digital buttons.
+//!
+//! Every digital [`Button`] is assigned a single bit of a `u32`, as used by
+//! [`DualSenseState::button_bits`] and [`DualSenseState::from_button_bits`]. This layout is part of
+//! the public API, so it is stable and can be safely stored or sent over the network. The bits
+//! from `19` onwards are reserved for future buttons and are always `0`.
+//!
+//! [`Button`]: enum@crate::mappings::Button
+//! [`DualSenseState::button_bits`]: fn@crate::DualSenseState::button_bits
+//! [`DualSenseState::from_button_bits`]: fn@crate::DualSenseState::from_button_bits
+
+/// The up arrow of the directional pad.
+pub const DPAD_UP: u32 = 1 << 0;
+/// The right arrow of the directional pad.
+pub const DPAD_RIGHT: u32 = 1 << 1;
+/// The down arrow of the directional pad.
+pub const DPAD_DOWN: u32 = 1 << 2;
+/// The left arrow of the directional pad.
+pub const DPAD_LEFT: u32 = 1 << 3;
+/// The `Square` button.
+pub const SQUARE: u32 = 1 << 4;
+/// The `Cross` button.
+pub const CROSS: u32 = 1 << 5;
+/// The `Circle` button.
+pub const CIRCLE: u32 = 1 << 6;
+/// The `Triangle` button.
+pub const TRIANGLE: u32 = 1 << 7;
+/// The `L1` button.
+pub const L1: u32 = 1 << 8;
+/// The `R1` button.
+pub const R1: u32 = 1 << 9;
+/// The `L2` trigger.
+pub const L2: u32 = 1 << 10;
+/// The `R2` trigger.
+pub const R2: u32 = 1 << 11;
+/// The left analog stick button.
+pub const L3: u32 = 1 << 12;
+/// The right analog stick button.
+pub const R3: u32 = 1 << 13;
+/// The `Create` button.
+pub const CREATE: u32 = 1 << 14;
+/// The `Options` button.
+pub const OPTIONS: u32 = 1 << 15;
+/// The `Home (PS)` button.
+pub const HOME: u32 = 1 << 16;
+/// The `Mute` button.
+pub const MUTE: u32 = 1 << 17;
+/// The touchpad button.
+pub const TOUCHPAD: u32 = 1 << 18;
@@ -10,7 +10,8 @@ use crate::mappings::{
 ///
 /// Action buttons are defined as the buttons present in the right part of the controller,
 /// represented as symbols (square, triangle, circle and cross to be exact).
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct ActionButtonGroup {
     /// A state of the square button.
     pub(crate) square: ButtonState,
@@ -25,7 +26,8 @@ pub(crate) struct ActionButtonGroup {
 /// A group of the front triggers.
 ///
 /// Front triggers are defined as the front buttons on top of the controller, labeled L1 and R1.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct FrontTriggerGroup {
     /// A state of the L1 trigger button.
     pub(crate) l1: ButtonState,
@@ -36,7 +38,8 @@ pub(crate) struct FrontTriggerGroup {
 /// A group of the back triggers.
 ///
 /// Back triggers are defined as the back buttons on top of the controller, labeled L2 and R2.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct BackTriggerGroup {
     /// A state of the L2 trigger button.
     pub(crate) l2: BackTriggerState,
@@ -45,7 +48,8 @@ pub(crate) struct BackTriggerGroup {
 }
 
 /// A analog stick group.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct StickGroup {
     /// Left analog stick (L3).
     pub(crate) left: StickState,
@@ -54,7 +58,8 @@ pub(crate) struct StickGroup {
 }
 
 /// A menu button group.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct MenuGroup {
     /// Create button.
     pub(crate) create: ButtonState,
@@ -67,17 +72,19 @@ pub(crate) struct MenuGroup {
 }
 
 /// A group of the controller power.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct PowerGroup {
     /// The state of the power.
     pub(crate) state: PowerState,
-    /// Controller charged percent.
-    pub(crate) percent: u8,
+    /// Controller charge level, in steps of 10%.
+    pub(crate) level: u8,
 }
 
 /// A group of external plugged devices.
 #[allow(missing_docs)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct PluggedGroup {
     /// The state of the headphone.
     pub(crate) headphone: PluggedState,
@@ -85,8 +92,45 @@ pub(crate) struct PluggedGroup {
     pub(crate) microphone: MicrophoneState,
     /// The state of the USB.
     pub(crate) usb: USBState,
-    // Allow unused while I try to understand it.
     // TODO: Understand it.
-    #[allow(unused)]
     pub(crate) haptic_low_pass_filter: PluggedState,
 }
+
+/// A group of the extra buttons of the DualSense Edge.
+///
+/// The DualSense Edge has two function buttons, below the analog sticks, and two back paddles,
+/// which can be attached to the back of the controller.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeButtons {
+    /// Left function button (FnL).
+    pub(crate) left_function: ButtonState,
+    /// Right function button (FnR).
+    pub(crate) right_function: ButtonState,
+    /// Left back paddle.
+    pub(crate) left_paddle: ButtonState,
+    /// Right back paddle.
+    pub(crate) right_paddle: ButtonState,
+}
+
+impl EdgeButtons {
+    /// Get the left function button (FnL) state.
+    pub fn left_function(&self) -> ButtonState {
+        self.left_function
+    }
+
+    /// Get the right function button (FnR) state.
+    pub fn right_function(&self) -> ButtonState {
+        self.right_function
+    }
+
+    /// Get the left back paddle state.
+    pub fn left_paddle(&self) -> ButtonState {
+        self.left_paddle
+    }
+
+    /// Get the right back paddle state.
+    pub fn right_paddle(&self) -> ButtonState {
+        self.right_paddle
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! States are atomic parts of the controller.
 
+use crate::info::Calibration;
+use crate::mappings::bits;
+use crate::DualSenseState;
+
 /// The state of a button.
 ///
 /// In the DualSense controller, where all the buttons are digital, there can be only two states,
 /// released and pressed.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ButtonState {
     /// The button is released.
     Released,
@@ -21,8 +26,111 @@ impl ButtonState {
     }
 }
 
+/// A digital button of the controller.
+///
+/// Every button that can only be either pressed or released is listed here. This includes the
+/// directional pad arrows and the digital state of the back triggers (see
+/// [`BackTriggerState::button`]).
+///
+/// [`BackTriggerState::button`]: fn@crate::mappings::BackTriggerState::button
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Button {
+    /// The up arrow of the directional pad.
+    DPadUp,
+    /// The right arrow of the directional pad.
+    DPadRight,
+    /// The down arrow of the directional pad.
+    DPadDown,
+    /// The left arrow of the directional pad.
+    DPadLeft,
+    /// The `Square` button.
+    Square,
+    /// The `Cross` button.
+    Cross,
+    /// The `Circle` button.
+    Circle,
+    /// The `Triangle` button.
+    Triangle,
+    /// The `L1` button.
+    L1,
+    /// The `R1` button.
+    R1,
+    /// The `L2` trigger.
+    L2,
+    /// The `R2` trigger.
+    R2,
+    /// The left analog stick button.
+    L3,
+    /// The right analog stick button.
+    R3,
+    /// The `Create` button.
+    Create,
+    /// The `Options` button.
+    Options,
+    /// The `Home (PS)` button.
+    Home,
+    /// The `Mute` button.
+    Mute,
+    /// The touchpad button (pressing the touchpad down).
+    TouchPad,
+}
+
+impl Button {
+    /// All the digital buttons of the controller.
+    pub const ALL: [Button; 19] = [
+        Button::DPadUp,
+        Button::DPadRight,
+        Button::DPadDown,
+        Button::DPadLeft,
+        Button::Square,
+        Button::Cross,
+        Button::Circle,
+        Button::Triangle,
+        Button::L1,
+        Button::R1,
+        Button::L2,
+        Button::R2,
+        Button::L3,
+        Button::R3,
+        Button::Create,
+        Button::Options,
+        Button::Home,
+        Button::Mute,
+        Button::TouchPad,
+    ];
+
+    /// Get the bit assigned to the button, as defined in [`bits`].
+    ///
+    /// [`bits`]: mod@crate::mappings::bits
+    pub const fn bit(self) -> u32 {
+        match self {
+            Button::DPadUp => bits::DPAD_UP,
+            Button::DPadRight => bits::DPAD_RIGHT,
+            Button::DPadDown => bits::DPAD_DOWN,
+            Button::DPadLeft => bits::DPAD_LEFT,
+            Button::Square => bits::SQUARE,
+            Button::Cross => bits::CROSS,
+            Button::Circle => bits::CIRCLE,
+            Button::Triangle => bits::TRIANGLE,
+            Button::L1 => bits::L1,
+            Button::R1 => bits::R1,
+            Button::L2 => bits::L2,
+            Button::R2 => bits::R2,
+            Button::L3 => bits::L3,
+            Button::R3 => bits::R3,
+            Button::Create => bits::CREATE,
+            Button::Options => bits::OPTIONS,
+            Button::Home => bits::HOME,
+            Button::Mute => bits::MUTE,
+            Button::TouchPad => bits::TOUCHPAD,
+        }
+    }
+}
+
 /// A back trigger axis.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct Axis(u8);
 
 impl Axis {
@@ -36,7 +144,8 @@ impl Axis {
 }
 
 /// Coordinates of the analog stick.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct StickCoordinates {
     /// X coordinate.
     pub(crate) x: u8,
@@ -44,8 +153,16 @@ pub(crate) struct StickCoordinates {
     pub(crate) y: u8,
 }
 
+/// The raw coordinate of an analog stick at rest.
+///
+/// A raw coordinate ranges from `0` to `255`, so there is no exact center. This crate uses `128`
+/// as the center for everything that depends on it, such as the normalized coordinates and the
+/// deadzones.
+pub const STICK_CENTER: u8 = 128;
+
 /// The state of the analog stick.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StickState {
     pub(crate) state: ButtonState,
     pub(crate) position: StickCoordinates,
@@ -66,10 +183,260 @@ impl StickState {
     pub fn y(&self) -> u8 {
         self.position.y
     }
+
+    /// Return `true` if both coordinates are at most `tolerance` away from the [`STICK_CENTER`]
+    /// and `false` otherwise.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[1..=4].copy_from_slice(&[125, 130, 128, 140]);
+    /// report[8] = 0x08;
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert!(state.left_stick().is_centered(3));
+    /// assert!(!state.left_stick().is_centered(2));
+    /// assert!(!state.right_stick().is_centered(10));
+    /// ```
+    ///
+    /// [`STICK_CENTER`]: crate::mappings::STICK_CENTER
+    pub fn is_centered(&self, tolerance: u8) -> bool {
+        self.position.x.abs_diff(STICK_CENTER) <= tolerance
+            && self.position.y.abs_diff(STICK_CENTER) <= tolerance
+    }
+
+    /// Get the `X` coordinate of the analog stick, normalized from `-1.0` (left) to `1.0` (right).
+    pub fn x_normalized(&self) -> f32 {
+        normalize_stick(self.position.x)
+    }
+
+    /// Get the `Y` coordinate of the analog stick, normalized from `-1.0` (down) to `1.0` (up).
+    ///
+    /// Note that the raw [`y`] coordinate grows downwards, so the normalized one has the opposite
+    /// sign, following the same convention as [`DPadDirection::as_vector`].
+    ///
+    /// [`y`]: fn@Self::y
+    /// [`DPadDirection::as_vector`]: fn@crate::mappings::DPadDirection::as_vector
+    pub fn y_normalized(&self) -> f32 {
+        -normalize_stick(self.position.y)
+    }
+
+    /// Get the coordinates of the analog stick normalized, with a radial `deadzone` applied.
+    ///
+    /// Positions whose distance from the center is below `deadzone` are read as `(0.0, 0.0)`. The
+    /// remaining positions are rescaled, so the output still ranges smoothly from `0.0` to `1.0`
+    /// outside of the deadzone. The `deadzone` is clamped from `0.0` to `0.9`.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[1..=4].copy_from_slice(&[140, 128, 255, 128]);
+    /// report[8] = 0x08;
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(state.left_stick().with_deadzone(0.1), (0.0, 0.0));
+    /// assert_eq!(state.right_stick().with_deadzone(0.1), (1.0, 0.0));
+    /// ```
+    pub fn with_deadzone(&self, deadzone: f32) -> (f32, f32) {
+        apply_deadzone(self.x_normalized(), self.y_normalized(), deadzone)
+    }
+}
+
+/// Apply a radial `deadzone` to normalized stick coordinates.
+///
+/// See [`StickState::with_deadzone`] for more information.
+pub(crate) fn apply_deadzone(x: f32, y: f32, deadzone: f32) -> (f32, f32) {
+    let deadzone = deadzone.clamp(0.0, 0.9);
+
+    let magnitude = x.hypot(y);
+    if magnitude <= deadzone {
+        return (0.0, 0.0);
+    }
+
+    let scale = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0) / magnitude;
+
+    (x * scale, y * scale)
+}
+
+/// The largest center offset learned by a [`StickCalibration`], in normalized units.
+const MAX_STICK_OFFSET: f32 = 0.25;
+
+/// The resting position of the analog sticks.
+///
+/// Worn analog sticks often rest off-center, which is read as a small, constant deflection. The
+/// calibration learns the resting position of each stick from states read while the sticks are
+/// not being touched, so it can be subtracted from the normalized coordinates. See
+/// [`DualSense::calibrate_sticks`] for how to learn and apply it.
+///
+/// ```rust
+/// use duplosentido::mappings::StickCalibration;
+/// use duplosentido::DualSenseState;
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[1..=4].copy_from_slice(&[140, 128, 128, 255]);
+/// report[8] = 0x08;
+/// let state = DualSenseState::from_raw(report).unwrap();
+///
+/// let calibration = StickCalibration::from_samples(&[state, state]);
+/// let (x, y) = calibration.left();
+/// assert!((x - 12.0 / 127.0).abs() < 1e-6 && y == 0.0);
+///
+/// // The right stick was held down during the calibration, so its offset is clamped.
+/// assert_eq!(calibration.right(), (0.0, -0.25));
+/// ```
+///
+/// [`DualSense::calibrate_sticks`]: fn@crate::DualSense::calibrate_sticks
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StickCalibration {
+    /// The resting position of the left stick.
+    left: (f32, f32),
+    /// The resting position of the right stick.
+    right: (f32, f32),
+}
+
+impl StickCalibration {
+    /// Learn the resting position of the sticks from `states` read while they were not touched.
+    ///
+    /// The resting position is the average of the normalized coordinates of every state, clamped
+    /// from `-0.25` to `0.25` on each axis, so a stick held off-center during the calibration does
+    /// not ruin the mapping. Without any state, the sticks are assumed to rest at the center.
+    pub fn from_samples(states: &[DualSenseState]) -> Self {
+        if states.is_empty() {
+            return StickCalibration::default();
+        }
+
+        let average = |stick: fn(&DualSenseState) -> StickState| {
+            let (x, y) = states.iter().map(stick).fold((0.0, 0.0), |(x, y), s| {
+                (x + s.x_normalized(), y + s.y_normalized())
+            });
+            let count = states.len() as f32;
+            let clamp = |v: f32| (v / count).clamp(-MAX_STICK_OFFSET, MAX_STICK_OFFSET);
+
+            (clamp(x), clamp(y))
+        };
+
+        StickCalibration {
+            left: average(DualSenseState::left_stick),
+            right: average(DualSenseState::right_stick),
+        }
+    }
+
+    /// Get the resting position of the left stick, as normalized `X` and `Y` coordinates.
+    pub fn left(&self) -> (f32, f32) {
+        self.left
+    }
+
+    /// Get the resting position of the right stick, as normalized `X` and `Y` coordinates.
+    pub fn right(&self) -> (f32, f32) {
+        self.right
+    }
+}
+
+/// Normalize a raw analog stick coordinate from `-1.0` to `1.0`, centered at [`STICK_CENTER`].
+fn normalize_stick(value: u8) -> f32 {
+    ((value as f32 - STICK_CENTER as f32) / 127.0).clamp(-1.0, 1.0)
+}
+
+/// The smallest maximum axis learned by a [`TriggerCalibration`].
+const MIN_TRIGGER_MAX: u8 = 128;
+
+/// The maximum axis reached by the back triggers.
+///
+/// Worn back triggers may no longer reach an [`axis`] of `255` when fully pulled. The calibration
+/// learns the maximum axis of each trigger, so a fully pulled trigger is still normalized to
+/// `1.0`. See [`DualSense::calibrate_triggers`] for how to learn and apply it.
+///
+/// ```rust
+/// use duplosentido::mappings::TriggerCalibration;
+/// use duplosentido::DualSenseState;
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[8] = 0x08;
+/// report[5..=6].copy_from_slice(&[230, 20]); // L2 and R2 axes.
+/// let state = DualSenseState::from_raw(report).unwrap();
+///
+/// let calibration = TriggerCalibration::from_samples(&[state]);
+/// assert_eq!(calibration.l2_max(), 230);
+/// assert_eq!(calibration.l2_normalized(&state), 1.0);
+///
+/// // R2 was barely pulled during the calibration, so its maximum is clamped.
+/// assert_eq!(calibration.r2_max(), 128);
+/// ```
+///
+/// [`axis`]: fn@crate::mappings::BackTriggerState::axis
+/// [`DualSense::calibrate_triggers`]: fn@crate::DualSense::calibrate_triggers
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriggerCalibration {
+    /// The maximum axis of the L2 trigger.
+    l2_max: u8,
+    /// The maximum axis of the R2 trigger.
+    r2_max: u8,
+}
+
+impl TriggerCalibration {
+    /// Learn the maximum axis of the back triggers from `states` read while they were pulled.
+    ///
+    /// The maximum axis is the largest one found in `states`, but never below `128`, so a trigger
+    /// that was not fully pulled during the calibration does not become overly sensitive.
+    pub fn from_samples(states: &[DualSenseState]) -> Self {
+        let max = |trigger: fn(&DualSenseState) -> BackTriggerState| {
+            states
+                .iter()
+                .map(|state| trigger(state).axis())
+                .fold(MIN_TRIGGER_MAX, u8::max)
+        };
+
+        TriggerCalibration {
+            l2_max: max(DualSenseState::l2),
+            r2_max: max(DualSenseState::r2),
+        }
+    }
+
+    /// Get the maximum axis of the L2 trigger.
+    pub fn l2_max(&self) -> u8 {
+        self.l2_max
+    }
+
+    /// Get the maximum axis of the R2 trigger.
+    pub fn r2_max(&self) -> u8 {
+        self.r2_max
+    }
+
+    /// Get the axis of the L2 trigger in `state`, normalized from `0.0` to `1.0` by its maximum.
+    pub fn l2_normalized(&self, state: &DualSenseState) -> f32 {
+        (state.l2().axis() as f32 / self.l2_max as f32).min(1.0)
+    }
+
+    /// Get the axis of the R2 trigger in `state`, normalized from `0.0` to `1.0` by its maximum.
+    pub fn r2_normalized(&self, state: &DualSenseState) -> f32 {
+        (state.r2().axis() as f32 / self.r2_max as f32).min(1.0)
+    }
 }
 
+impl Default for TriggerCalibration {
+    /// Assume the back triggers reach the full axis.
+    fn default() -> Self {
+        TriggerCalibration {
+            l2_max: u8::MAX,
+            r2_max: u8::MAX,
+        }
+    }
+}
+
+/// Nominal resolution of the gyroscope, in units per degree per second.
+pub(crate) const GYROSCOPE_RESOLUTION: f32 = 16.0;
+
 /// Angular velocity of the controller (used for understanding orientation).
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AngularVelocityState {
     /// X velocity.
     pub(crate) x: i16,
@@ -94,10 +461,108 @@ impl AngularVelocityState {
     pub fn z(&self) -> i16 {
         self.z
     }
+
+    /// Get the `X` component of the angular velocity in degrees per second.
+    ///
+    /// The conversion uses the nominal resolution of the gyroscope, which is approximately `16`
+    /// units per degree per second. As such, it is only an approximation that does not take in
+    /// account the calibration of each controller.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[16..=17].copy_from_slice(&1600_i16.to_le_bytes());
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(state.gyroscope().x(), 1600);
+    /// assert_eq!(state.gyroscope().x_dps(), 100.0);
+    /// ```
+    pub fn x_dps(&self) -> f32 {
+        self.x as f32 / GYROSCOPE_RESOLUTION
+    }
+
+    /// Get the `Y` component of the angular velocity in degrees per second.
+    ///
+    /// See [`x_dps`] for more information about the conversion.
+    ///
+    /// [`x_dps`]: fn@Self::x_dps
+    pub fn y_dps(&self) -> f32 {
+        self.y as f32 / GYROSCOPE_RESOLUTION
+    }
+
+    /// Get the `Z` component of the angular velocity in degrees per second.
+    ///
+    /// See [`x_dps`] for more information about the conversion.
+    ///
+    /// [`x_dps`]: fn@Self::x_dps
+    pub fn z_dps(&self) -> f32 {
+        self.z as f32 / GYROSCOPE_RESOLUTION
+    }
+
+    /// Get the angular velocity in degrees per second, using the controller `calibration`.
+    ///
+    /// Unlike [`x_dps`], [`y_dps`] and [`z_dps`], the conversion takes in account the bias and
+    /// resolution of the controller gyroscope, so it is more accurate. The components are returned
+    /// in `X`, `Y` and `Z` order. See [`Calibration`] for more information.
+    ///
+    /// [`x_dps`]: fn@Self::x_dps
+    /// [`y_dps`]: fn@Self::y_dps
+    /// [`z_dps`]: fn@Self::z_dps
+    /// [`Calibration`]: struct@crate::info::Calibration
+    pub fn calibrated_dps(&self, calibration: &Calibration) -> [f32; 3] {
+        calibration.gyroscope([self.x, self.y, self.z])
+    }
+
+    /// Get the raw angular velocity, in `X`, `Y` and `Z` order.
+    ///
+    /// This is convenient to build the vector types of math libraries (e.g. `glam` or
+    /// `nalgebra`) without calling each getter:
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[16..=17].copy_from_slice(&160_i16.to_le_bytes());
+    /// report[18..=19].copy_from_slice(&(-320_i16).to_le_bytes());
+    /// let gyroscope = DualSenseState::from_raw(report).unwrap().gyroscope();
+    ///
+    /// assert_eq!(gyroscope.as_array(), [gyroscope.x(), gyroscope.y(), gyroscope.z()]);
+    /// assert_eq!(gyroscope.as_array_f32(), [10.0, 0.0, -20.0]);
+    /// ```
+    pub fn as_array(&self) -> [i16; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Get the angular velocity in degrees per second, in `X`, `Y` and `Z` order.
+    ///
+    /// The components are the same as [`x_dps`], [`y_dps`] and [`z_dps`], so the conversion uses
+    /// the nominal resolution of the gyroscope. For a more accurate conversion, see
+    /// [`calibrated_dps`].
+    ///
+    /// [`x_dps`]: fn@Self::x_dps
+    /// [`y_dps`]: fn@Self::y_dps
+    /// [`z_dps`]: fn@Self::z_dps
+    /// [`calibrated_dps`]: fn@Self::calibrated_dps
+    pub fn as_array_f32(&self) -> [f32; 3] {
+        [self.x_dps(), self.y_dps(), self.z_dps()]
+    }
 }
 
+/// Nominal resolution of the accelerometer, in units per g.
+pub(crate) const ACCELEROMETER_RESOLUTION: f32 = 8192.0;
+
 /// Acceleration of the controller (used for understanding movement).
-#[derive(Debug, Copy, Clone)]
+///
+/// The axes are relative to the controller body: `X` points to the right of the controller, `Y`
+/// points up (out of the face with the buttons) and `Z` points towards the player holding it. As
+/// such, a controller at rest on a table measures approximately `+1 g` in the `Y` axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccelerationState {
     /// X acceleration.
     pub(crate) x: i16,
@@ -122,11 +587,175 @@ impl AccelerationState {
     pub fn z(&self) -> i16 {
         self.z
     }
+
+    /// Get the `X` component of the acceleration in units of g.
+    ///
+    /// The conversion uses the nominal resolution of the accelerometer, which is approximately
+    /// `8192` units per g. As such, it is only an approximation that does not take in account the
+    /// calibration of each controller.
+    pub fn x_g(&self) -> f32 {
+        self.x as f32 / ACCELEROMETER_RESOLUTION
+    }
+
+    /// Get the `Y` component of the acceleration in units of g.
+    ///
+    /// See [`x_g`] for more information about the conversion.
+    ///
+    /// [`x_g`]: fn@Self::x_g
+    pub fn y_g(&self) -> f32 {
+        self.y as f32 / ACCELEROMETER_RESOLUTION
+    }
+
+    /// Get the `Z` component of the acceleration in units of g.
+    ///
+    /// See [`x_g`] for more information about the conversion.
+    ///
+    /// [`x_g`]: fn@Self::x_g
+    pub fn z_g(&self) -> f32 {
+        self.z as f32 / ACCELEROMETER_RESOLUTION
+    }
+
+    /// Get the acceleration in units of g, using the controller `calibration`.
+    ///
+    /// Unlike [`x_g`], [`y_g`] and [`z_g`], the conversion takes in account the bias and
+    /// resolution of the controller accelerometer, so it is more accurate. The components are
+    /// returned in `X`, `Y` and `Z` order. See [`Calibration`] for more information.
+    ///
+    /// [`x_g`]: fn@Self::x_g
+    /// [`y_g`]: fn@Self::y_g
+    /// [`z_g`]: fn@Self::z_g
+    /// [`Calibration`]: struct@crate::info::Calibration
+    pub fn calibrated_g(&self, calibration: &Calibration) -> [f32; 3] {
+        calibration.accelerometer([self.x, self.y, self.z])
+    }
+
+    /// Get the raw acceleration, in `X`, `Y` and `Z` order.
+    ///
+    /// This is convenient to build the vector types of math libraries (e.g. `glam` or
+    /// `nalgebra`) without calling each getter:
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[24..=25].copy_from_slice(&8192_i16.to_le_bytes());
+    /// let acceleration = DualSenseState::from_raw(report).unwrap().acceleration();
+    ///
+    /// assert_eq!(acceleration.as_array(), [0, 8192, 0]);
+    /// assert_eq!(acceleration.as_array_f32(), [0.0, 1.0, 0.0]);
+    /// ```
+    pub fn as_array(&self) -> [i16; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    /// Get the acceleration in units of g, in `X`, `Y` and `Z` order.
+    ///
+    /// The components are the same as [`x_g`], [`y_g`] and [`z_g`], so the conversion uses the
+    /// nominal resolution of the accelerometer. For a more accurate conversion, see
+    /// [`calibrated_g`].
+    ///
+    /// [`x_g`]: fn@Self::x_g
+    /// [`y_g`]: fn@Self::y_g
+    /// [`z_g`]: fn@Self::z_g
+    /// [`calibrated_g`]: fn@Self::calibrated_g
+    pub fn as_array_f32(&self) -> [f32; 3] {
+        [self.x_g(), self.y_g(), self.z_g()]
+    }
+
+    /// Get the magnitude of the acceleration in units of g.
+    ///
+    /// Since gravity is always acting on the controller, the magnitude is approximately `1.0` when
+    /// the controller is at rest.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[24..=25].copy_from_slice(&8192_i16.to_le_bytes());
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(state.acceleration().y_g(), 1.0);
+    /// assert_eq!(state.acceleration().magnitude_g(), 1.0);
+    /// ```
+    pub fn magnitude_g(&self) -> f32 {
+        let (x, y, z) = (self.x_g(), self.y_g(), self.z_g());
+
+        (x * x + y * y + z * z).sqrt()
+    }
+
+    /// Get the pitch (forward and backward tilt) of the controller, in radians.
+    ///
+    /// The pitch ranges from `-π/2` to `π/2`, and is positive when the top of the controller (the
+    /// side away from the player) is tilted up. See [`roll`] for more information about how the
+    /// tilt is computed, and its limitations.
+    ///
+    /// [`roll`]: fn@Self::roll
+    pub fn pitch(&self) -> f32 {
+        let (x, y, z) = (self.x as f32, self.y as f32, self.z as f32);
+
+        (-z).atan2(x.hypot(y))
+    }
+
+    /// Get the roll (sideways tilt) of the controller, in radians.
+    ///
+    /// The roll ranges from `-π` to `π`, and is positive when the right side of the controller is
+    /// tilted down. The tilt is computed from the direction of gravity, so it does not drift over
+    /// time like an integrated gyroscope, but it is only valid while the controller is close to
+    /// rest: any other acceleration (e.g. the controller being moved) is mistaken for a tilt. Also,
+    /// the rotation around the vertical axis (the yaw) can not be computed from gravity. For a
+    /// complete orientation, see [`Imu`].
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    /// use std::f32::consts::FRAC_PI_2;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[24..=25].copy_from_slice(&8192_i16.to_le_bytes());
+    /// let flat = DualSenseState::from_raw(report).unwrap().acceleration();
+    ///
+    /// assert_eq!((flat.pitch(), flat.roll()), (0.0, 0.0));
+    ///
+    /// // The right side of the controller pointing down.
+    /// report[22..=23].copy_from_slice(&(-8192_i16).to_le_bytes());
+    /// report[24..=25].copy_from_slice(&0_i16.to_le_bytes());
+    /// let tilted = DualSenseState::from_raw(report).unwrap().acceleration();
+    ///
+    /// assert!((tilted.roll() - FRAC_PI_2).abs() < 1e-6);
+    /// assert!(tilted.pitch().abs() < 1e-6);
+    /// ```
+    ///
+    /// [`Imu`]: struct@crate::imu::Imu
+    pub fn roll(&self) -> f32 {
+        let (x, y) = (self.x as f32, self.y as f32);
+
+        (-x).atan2(y)
+    }
 }
 
 // TODO: Maybe change types to be all 32-bits.
 /// Temperature of the controller.
-#[derive(Debug, Copy, Clone)]
+///
+/// Equality compares both the unit and the value, so `Celsius(0)` is *not* equal to
+/// `Fahrenheit(32)`. Convert both temperatures to the same unit before comparing them:
+///
+/// ```rust
+/// use duplosentido::mappings::TemperatureState;
+///
+/// let freezing = TemperatureState::Celsius(0);
+///
+/// assert_eq!(freezing.as_fahrenheit(), TemperatureState::Fahrenheit(32));
+/// assert_eq!(freezing.as_kelvin(), TemperatureState::Kelvin(273));
+/// assert_eq!(TemperatureState::Fahrenheit(32).as_celsius(), freezing);
+/// assert_eq!(TemperatureState::Kelvin(273).as_celsius(), freezing);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TemperatureState {
     /// Temperature in Celsius.
     Celsius(i8),
@@ -138,37 +767,138 @@ pub enum TemperatureState {
 
 impl TemperatureState {
     /// Return the temperature as Celsius.
-    pub fn as_celcius(&self) -> Self {
+    ///
+    /// The conversion is made with floating point math and then truncated, saturating at the
+    /// bounds of the unit. See [`as_celsius_f32`] for the precise value. Temperatures below
+    /// freezing are converted correctly:
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::TemperatureState;
+    ///
+    /// assert_eq!(
+    ///     TemperatureState::Fahrenheit(0).as_celsius(),
+    ///     TemperatureState::Celsius(-17)
+    /// );
+    /// assert_eq!(
+    ///     TemperatureState::Kelvin(0).as_celsius(),
+    ///     TemperatureState::Celsius(-128)
+    /// );
+    /// ```
+    ///
+    /// [`as_celsius_f32`]: fn@Self::as_celsius_f32
+    pub fn as_celsius(&self) -> Self {
         match *self {
             Self::Celsius(t) => Self::Celsius(t),
-            Self::Fahrenheit(t) => Self::Celsius(((t - 32) as f32 * 5.0 / 9.0) as i8),
-            Self::Kelvin(t) => Self::Celsius((t - 273) as i8),
+            _ => Self::Celsius(self.as_celsius_f32() as i8),
         }
     }
 
+    /// Return the temperature as Celsius.
+    #[deprecated(note = "use as_celsius")]
+    pub fn as_celcius(&self) -> Self {
+        self.as_celsius()
+    }
+
     /// Return the temperature as Fahrenheit.
+    ///
+    /// The conversion is made with floating point math and then truncated, saturating at the
+    /// bounds of the unit, so temperatures below 0°F are returned as `Fahrenheit(0)`. See
+    /// [`as_fahrenheit_f32`] for the precise value.
+    ///
+    /// [`as_fahrenheit_f32`]: fn@Self::as_fahrenheit_f32
     pub fn as_fahrenheit(&self) -> Self {
         match *self {
-            Self::Celsius(t) => Self::Fahrenheit((t as f32 * (9.0 / 5.0)) as u8 + 32),
             Self::Fahrenheit(t) => Self::Fahrenheit(t),
-            Self::Kelvin(t) => Self::Fahrenheit(((t - 273) as f32 * (9.0 / 5.0)) as u8 + 32),
+            _ => Self::Fahrenheit(self.as_fahrenheit_f32() as u8),
         }
     }
 
     /// Return the temperature as Kelvin.
+    ///
+    /// The conversion is made with floating point math and then truncated. See [`as_kelvin_f32`]
+    /// for the precise value.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::TemperatureState;
+    ///
+    /// assert_eq!(
+    ///     TemperatureState::Fahrenheit(0).as_kelvin(),
+    ///     TemperatureState::Kelvin(255)
+    /// );
+    /// ```
+    ///
+    /// [`as_kelvin_f32`]: fn@Self::as_kelvin_f32
     pub fn as_kelvin(&self) -> Self {
         match *self {
-            Self::Celsius(t) => Self::Kelvin((t as f32 + 273.15) as u16),
-            Self::Fahrenheit(t) => Self::Kelvin(((t - 32) as f32 * 5.0 / 9.0) as u16 - 273),
             Self::Kelvin(t) => Self::Kelvin(t),
+            _ => Self::Kelvin(self.as_kelvin_f32() as u16),
+        }
+    }
+
+    /// Get the temperature in Celsius.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::TemperatureState;
+    ///
+    /// assert_eq!(TemperatureState::Fahrenheit(32).as_celsius_f32(), 0.0);
+    /// assert!((TemperatureState::Kelvin(273).as_celsius_f32() + 0.15).abs() < 1e-4);
+    /// ```
+    pub fn as_celsius_f32(&self) -> f32 {
+        match *self {
+            Self::Celsius(t) => t as f32,
+            Self::Fahrenheit(t) => (t as f32 - 32.0) * 5.0 / 9.0,
+            Self::Kelvin(t) => t as f32 - 273.15,
+        }
+    }
+
+    /// Get the temperature in Fahrenheit.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::TemperatureState;
+    ///
+    /// assert_eq!(TemperatureState::Celsius(0).as_fahrenheit_f32(), 32.0);
+    /// assert_eq!(TemperatureState::Celsius(-40).as_fahrenheit_f32(), -40.0);
+    /// ```
+    pub fn as_fahrenheit_f32(&self) -> f32 {
+        match *self {
+            Self::Fahrenheit(t) => t as f32,
+            _ => self.as_celsius_f32() * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    /// Get the temperature in Kelvin.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::TemperatureState;
+    ///
+    /// assert_eq!(TemperatureState::Celsius(0).as_kelvin_f32(), 273.15);
+    /// assert_eq!(TemperatureState::Fahrenheit(32).as_kelvin_f32(), 273.15);
+    /// ```
+    pub fn as_kelvin_f32(&self) -> f32 {
+        match *self {
+            Self::Kelvin(t) => t as f32,
+            _ => self.as_celsius_f32() + 273.15,
         }
     }
 }
 
+/// The width of the touchpad, in touch coordinates.
+///
+/// The `X` coordinate of a touch ranges from `0` (left edge) to `TOUCHPAD_WIDTH - 1` (right
+/// edge).
+pub const TOUCHPAD_WIDTH: u16 = 1920;
+
+/// The height of the touchpad, in touch coordinates.
+///
+/// The `Y` coordinate of a touch ranges from `0` (top edge) to `TOUCHPAD_HEIGHT - 1` (bottom
+/// edge). Note that the touchpad is not physically 16:9, so the same coordinate distance is
+/// shorter along the `X` axis than along the `Y` axis.
+pub const TOUCHPAD_HEIGHT: u16 = 1080;
+
 /// Data of finger movement in the touchpad.
-#[allow(unused)]
-#[derive(Debug, Copy, Clone)]
-pub(crate) struct FingerData {
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FingerData {
     /// Index of the finger.
     ///
     /// Every time a finger touches the touchpad, the index is incremented by one, as to enable
@@ -182,20 +912,162 @@ pub(crate) struct FingerData {
     pub(crate) y: u16,
 }
 
+impl FingerData {
+    /// Get the index of the touch.
+    ///
+    /// Every time a finger touches the touchpad, the index is incremented by one (wrapping around
+    /// after `127`). As such, the index can be used to tell if the finger in a slot is still the
+    /// same one across multiple states.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Return `true` if the finger is touching the touchpad and `false` otherwise.
+    ///
+    /// When the finger is not touching, the coordinates are the ones of its last touch.
+    pub fn is_touching(&self) -> bool {
+        self.is_touching
+    }
+
+    /// Get the `X` coordinate of the touch, growing to the right.
+    pub fn x(&self) -> u16 {
+        self.x
+    }
+
+    /// Get the `Y` coordinate of the touch, growing downwards.
+    pub fn y(&self) -> u16 {
+        self.y
+    }
+
+    /// Get the `X` coordinate of the touch, normalized from `0.0` (left) to `1.0` (right).
+    ///
+    /// The coordinate is divided by [`TOUCHPAD_WIDTH`], so it can be mapped to any other space by
+    /// multiplying it by the width of that space:
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[33] = 0x01; // First finger touching.
+    /// // The 12-bit `X` (960) and `Y` (270) coordinates, packed together.
+    /// report[34] = 0xC0;
+    /// report[35] = 0xE3;
+    /// report[36] = 0x10;
+    /// report[37] = 0x80;
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// let touch = state.touchpad().touches().next().unwrap();
+    /// assert_eq!(touch.x_normalized(), 0.5);
+    /// assert_eq!(touch.y_normalized(), 0.25);
+    /// ```
+    ///
+    /// [`TOUCHPAD_WIDTH`]: crate::mappings::TOUCHPAD_WIDTH
+    pub fn x_normalized(&self) -> f32 {
+        self.x as f32 / TOUCHPAD_WIDTH as f32
+    }
+
+    /// Get the `Y` coordinate of the touch, normalized from `0.0` (top) to `1.0` (bottom).
+    ///
+    /// See [`x_normalized`] for more information.
+    ///
+    /// [`x_normalized`]: fn@Self::x_normalized
+    pub fn y_normalized(&self) -> f32 {
+        self.y as f32 / TOUCHPAD_HEIGHT as f32
+    }
+}
+
 /// The State of the touchpad.
-#[allow(unused)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TouchPadState {
     /// If the touchpad is being clicked or not.
     pub(crate) state: ButtonState,
     /// Finger data of up to two fingers.
     pub(crate) finger: [FingerData; 2],
-    /// TODO: understand and document.
+    /// Counter of the touchpad samples.
     pub(crate) timestamp: u8,
 }
 
+impl TouchPadState {
+    /// Get the timestamp of the touchpad sample.
+    ///
+    /// The timestamp is a counter that is incremented for every new touchpad sample, so it can be
+    /// used to measure the interval between touches (e.g. to compute the velocity of a gesture).
+    /// The length of each tick is not documented, so for absolute times the
+    /// [`DualSenseState::sensor_timestamp`] should be preferred.
+    ///
+    /// Since the counter is only 8 bits, it wraps around from `255` back to `0`. As such, the
+    /// difference between two timestamps should be computed with [`timestamp_delta`].
+    ///
+    /// [`DualSenseState::sensor_timestamp`]: fn@DualSenseState::sensor_timestamp
+    /// [`timestamp_delta`]: fn@Self::timestamp_delta
+    pub fn timestamp(&self) -> u8 {
+        self.timestamp
+    }
+
+    /// Get the number of ticks elapsed since the `previous` touchpad sample.
+    ///
+    /// The wrap around of the counter is taken in account, so the result is correct as long as
+    /// less than `256` ticks elapsed between both samples.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[41] = 250;
+    /// let previous = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// report[41] = 4;
+    /// let current = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(current.touchpad().timestamp(), 4);
+    /// assert_eq!(current.touchpad().timestamp_delta(&previous.touchpad()), 10);
+    /// ```
+    pub fn timestamp_delta(&self, previous: &TouchPadState) -> u8 {
+        self.timestamp.wrapping_sub(previous.timestamp)
+    }
+
+    /// Get the data of both finger slots, including the ones not touching the touchpad.
+    pub fn fingers(&self) -> [FingerData; 2] {
+        self.finger
+    }
+
+    /// Get an iterator over the fingers touching the touchpad.
+    ///
+    /// The fingers are yielded in the order of their slots, so a finger can be followed across
+    /// multiple states by its [`index`]:
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[33] = 0x80 | 0x01; // First slot not touching.
+    /// report[37] = 0x02; // Second slot touching at (1000, 500).
+    /// report[38..=40].copy_from_slice(&[0xE8, 0x43, 0x1F]);
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// let touches: Vec<_> = state.touchpad().touches().collect();
+    /// assert_eq!(touches.len(), 1);
+    /// assert_eq!(touches[0].index(), 2);
+    /// assert_eq!((touches[0].x(), touches[0].y()), (1000, 500));
+    /// ```
+    ///
+    /// [`index`]: fn@crate::mappings::FingerData::index
+    pub fn touches(&self) -> impl Iterator<Item = FingerData> {
+        self.finger.into_iter().filter(FingerData::is_touching)
+    }
+}
+
 /// The power state of the controller.
-#[derive(Debug, Copy, Clone)]
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerState {
     /// Controller is discharging.
     Discharging = 0x00,
@@ -209,10 +1081,57 @@ pub enum PowerState {
     AbnormalTemperature = 0x0B,
     /// Controller cannot charge.
     ChargingError = 0x0F,
+    /// Controller reported a state that is not known by this crate.
+    ///
+    /// Some firmwares report states not documented anywhere (e.g. `0x03` to `0x09`). Instead of
+    /// failing, the raw value is kept so it can still be inspected.
+    ///
+    /// ```rust
+    /// use duplosentido::{mappings::PowerState, DualSenseState};
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[53] = 0x35;
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(state.battery_status(), PowerState::Unknown(0x03));
+    /// ```
+    Unknown(u8),
+}
+
+impl PowerState {
+    /// Return `true` if the controller is charging and `false` otherwise.
+    pub fn is_charging(&self) -> bool {
+        matches!(self, PowerState::Charging)
+    }
+
+    /// Return `true` if the controller battery is full and `false` otherwise.
+    pub fn is_full(&self) -> bool {
+        matches!(self, PowerState::Complete)
+    }
+
+    /// Return `true` if the controller is in an abnormal state and `false` otherwise.
+    ///
+    /// An abnormal state is any state that indicates a problem with the battery or charging, such
+    /// as [`AbnormalVoltage`], [`AbnormalTemperature`] or [`ChargingError`].
+    ///
+    /// [`AbnormalVoltage`]: PowerState::AbnormalVoltage
+    /// [`AbnormalTemperature`]: PowerState::AbnormalTemperature
+    /// [`ChargingError`]: PowerState::ChargingError
+    pub fn is_fault(&self) -> bool {
+        matches!(
+            self,
+            PowerState::AbnormalVoltage
+                | PowerState::AbnormalTemperature
+                | PowerState::ChargingError
+        )
+    }
 }
 
 /// The state of a peripheral device.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PluggedState {
     /// The device is unplugged.
     Unplugged,
@@ -228,7 +1147,8 @@ impl PluggedState {
 }
 
 /// The state of a microphone muted status.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MutedState {
     /// The device is unmuted.
     Unmuted,
@@ -244,7 +1164,8 @@ impl MutedState {
 }
 
 /// The state of the microphone.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MicrophoneState {
     /// Is the microphone plugged.
     pub(crate) state: PluggedState,
@@ -282,7 +1203,8 @@ impl MicrophoneState {
 }
 
 /// The state of the USB.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct USBState {
     /// Is the USB data capable.
     pub(crate) data: PluggedState,
@@ -308,7 +1230,8 @@ impl USBState {
 /// [`BackTriggerEffect`]
 ///
 /// [`BackTriggerEffect`]: enum@crate::mappings::BackTriggerEffect
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BackTriggerStatus {
     /// No feedback load is being applied.
     FeedbackNoLoad,
@@ -324,12 +1247,21 @@ pub enum BackTriggerStatus {
     VibrationNotVibrating,
     /// Trigger is vibrating.
     VibrationIsVibrating,
+    /// Controller reported a status that is not known by this crate for the current effect.
+    ///
+    /// This covers any status the controller is not known to report for the current effect,
+    /// including every status of an [`Unknown`] effect. The raw value is kept so it can still be
+    /// inspected.
+    ///
+    /// [`Unknown`]: crate::mappings::BackTriggerEffect::Unknown
+    Unknown(u8),
 }
 
 /// The applied effect to the back trigger.
 ///
 /// Different effects apply different resistance curves and vibration patterns.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BackTriggerEffect {
     /// Turn the trigger effect off and return the trigger stop to the neutral position.
     ///
@@ -358,11 +1290,35 @@ pub enum BackTriggerEffect {
     ///
     /// [`BackTriggerStatus`]: enum@crate::mappings::BackTriggerStatus
     Vibration,
+    /// Controller reported an effect that is not known by this crate.
+    ///
+    /// Instead of failing, the raw value is kept so it can still be inspected:
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::{BackTriggerEffect, BackTriggerStatus};
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[42] = 0x20;
+    /// report[48] = 0x05;
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(state.r2().effect(), BackTriggerEffect::Unknown(0x05));
+    /// assert_eq!(state.r2().status(), BackTriggerStatus::Unknown(0x02));
+    /// ```
+    Unknown(u8),
 }
 
-// TODO: Understand better what stop location means.
-#[allow(missing_docs, unused)]
-#[derive(Debug, Copy, Clone)]
+/// The stop location of the back trigger.
+///
+/// See [`BackTriggerState::stop`] for more information.
+///
+/// [`BackTriggerState::stop`]: fn@crate::mappings::BackTriggerState::stop
+// TODO: Replace by an enum once the values are better understood.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BackTriggerStop(pub u8);
 
 /// A state of the back trigger.
@@ -371,7 +1327,8 @@ pub struct BackTriggerStop(pub u8);
 /// such, more information is needed to fully represent it.
 ///
 /// [`front trigger`]: enum@crate::mappings::ButtonState
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BackTriggerState {
     /// Binary state of the back trigger.
     ///
@@ -387,8 +1344,7 @@ pub struct BackTriggerState {
     ///
     /// The status relates to the effect.
     pub(crate) status: BackTriggerStatus,
-    // TODO: Understand it.
-    #[allow(unused)]
+    /// Stop location of the back trigger.
     pub(crate) stop: BackTriggerStop,
 }
 
@@ -411,11 +1367,75 @@ impl BackTriggerState {
     ///
     /// A common problem that can happen with time is the degradation of the back triggers due to
     /// persistent pressing. When that happens, the trigger can no longer achieve a value of `255`,
-    /// stopping at a lower amount.
+    /// stopping at a lower amount. See [`TriggerCalibration`] to compensate for it.
+    ///
+    /// [`TriggerCalibration`]: struct@crate::mappings::TriggerCalibration
     pub fn axis(&self) -> u8 {
         self.axis.value()
     }
 
+    /// Get the current axis of the back trigger, normalized from `0.0` to `1.0`.
+    ///
+    /// See [`axis`] for more information.
+    ///
+    /// [`axis`]: fn@Self::axis
+    pub fn axis_normalized(&self) -> f32 {
+        self.axis.value() as f32 / u8::MAX as f32
+    }
+
+    /// Return `true` if the back trigger is pressed past `threshold` and `false` otherwise.
+    ///
+    /// The `threshold` ranges from `0.0` to `1.0`, just like [`axis_normalized`]. This makes it
+    /// possible to treat a partial pull (e.g. 50%) as a digital press, different from [`button`],
+    /// which considers any pull as a press.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[5] = 100; // L2 axis.
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert!(state.l2().is_pressed_past(0.25));
+    /// assert!(!state.l2().is_pressed_past(0.5));
+    /// ```
+    ///
+    /// [`axis_normalized`]: fn@Self::axis_normalized
+    /// [`button`]: fn@Self::button
+    pub fn is_pressed_past(&self, threshold: f32) -> bool {
+        self.axis_normalized() > threshold
+    }
+
+    /// Return `true` if the back trigger axis is at least `threshold` and `false` otherwise.
+    ///
+    /// The `threshold` ranges from `0` to `255`, just like [`axis`], so a trigger pulled less than
+    /// it is considered released. This filters out accidental pulls, which many games do with a
+    /// threshold of about 10% (`26`). The default behavior of [`button`] remains unchanged, still
+    /// considering any pull as a press.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[6] = 10; // R2 axis.
+    /// report[9] = 0b0000_1000; // R2 pressed.
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert!(state.r2().button().is_pressed());
+    /// assert!(!state.r2().is_pressed_with(26));
+    /// assert!(state.r2().is_pressed_with(10));
+    /// ```
+    ///
+    /// [`axis`]: fn@Self::axis
+    /// [`button`]: fn@Self::button
+    pub fn is_pressed_with(&self, threshold: u8) -> bool {
+        self.axis.value() >= threshold
+    }
+
     /// Get the current effect of the back trigger.
     ///
     /// The DualSense controller has support for different pre-defined effects (they can change
@@ -438,6 +1458,31 @@ impl BackTriggerState {
     pub fn status(&self) -> BackTriggerStatus {
         self.status
     }
+
+    /// Get the current stop location of the back trigger.
+    ///
+    /// The meaning of this value is not officially documented. The current understanding is that
+    /// it is the zone of the trigger travel, from `0` to `9`, where the resistance of the effect
+    /// engaged, which matches the zones used to position the effects (see [`TriggerEffect`]).
+    /// While no effect is engaged, it is `0`. Since it is not fully understood, the raw value is
+    /// exposed as sent by the controller, ranging from `0` to `15`.
+    ///
+    /// ```rust
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// report[43] = 0x14; // L2 status and stop location.
+    /// let state = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(state.l2().stop(), 4);
+    /// ```
+    ///
+    /// [`TriggerEffect`]: enum@crate::output::TriggerEffect
+    pub fn stop(&self) -> u8 {
+        self.stop.0
+    }
 }
 
 /// Direction of the directional pad.
@@ -445,15 +1490,16 @@ impl BackTriggerState {
 /// The directional pad is defined as the four arrow buttons in the left of the face of the
 /// controller. At most two neighboring buttons can be pressed at once. Because of this, their
 /// state is usually represented as directions in a compass.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DPadDirection {
     /// Up arrow pressed.
     North,
-    /// Up and left arrow pressed.
+    /// Up and right arrow pressed.
     NorthEast,
-    /// Left arrow pressed.
+    /// Right arrow pressed.
     East,
-    /// Left and down arrow pressed.
+    /// Down and right arrow pressed.
     SouthEast,
     /// Down arrow pressed.
     South,
@@ -461,12 +1507,103 @@ pub enum DPadDirection {
     SouthWest,
     /// Left arrow pressed.
     West,
-    /// Left and up arrow pressed.
+    /// Up and left arrow pressed.
     NorthWest,
     /// No arrow pressed.
     None,
 }
 
+impl DPadDirection {
+    /// Return `true` if the up arrow is pressed and `false` otherwise.
+    ///
+    /// Diagonals press two arrows at once, so this is also `true` for [`NorthEast`] and
+    /// [`NorthWest`].
+    ///
+    /// [`NorthEast`]: DPadDirection::NorthEast
+    /// [`NorthWest`]: DPadDirection::NorthWest
+    pub fn up(&self) -> bool {
+        matches!(
+            self,
+            DPadDirection::North | DPadDirection::NorthEast | DPadDirection::NorthWest
+        )
+    }
+
+    /// Return `true` if the right arrow is pressed and `false` otherwise.
+    ///
+    /// Diagonals press two arrows at once, so this is also `true` for [`NorthEast`] and
+    /// [`SouthEast`].
+    ///
+    /// [`NorthEast`]: DPadDirection::NorthEast
+    /// [`SouthEast`]: DPadDirection::SouthEast
+    pub fn right(&self) -> bool {
+        matches!(
+            self,
+            DPadDirection::East | DPadDirection::NorthEast | DPadDirection::SouthEast
+        )
+    }
+
+    /// Return `true` if the down arrow is pressed and `false` otherwise.
+    ///
+    /// Diagonals press two arrows at once, so this is also `true` for [`SouthEast`] and
+    /// [`SouthWest`].
+    ///
+    /// [`SouthEast`]: DPadDirection::SouthEast
+    /// [`SouthWest`]: DPadDirection::SouthWest
+    pub fn down(&self) -> bool {
+        matches!(
+            self,
+            DPadDirection::South | DPadDirection::SouthEast | DPadDirection::SouthWest
+        )
+    }
+
+    /// Return `true` if the left arrow is pressed and `false` otherwise.
+    ///
+    /// Diagonals press two arrows at once, so this is also `true` for [`NorthWest`] and
+    /// [`SouthWest`].
+    ///
+    /// [`NorthWest`]: DPadDirection::NorthWest
+    /// [`SouthWest`]: DPadDirection::SouthWest
+    pub fn left(&self) -> bool {
+        matches!(
+            self,
+            DPadDirection::West | DPadDirection::NorthWest | DPadDirection::SouthWest
+        )
+    }
+
+    /// Get the direction as a `(x, y)` vector, where `x` points right and `y` points up.
+    ///
+    /// The vector always has a magnitude of `1.0`, except for [`None`], which returns
+    /// `(0.0, 0.0)`. As such, diagonals are normalized, so the directional pad can drive the same
+    /// code as an analog stick.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::DPadDirection;
+    ///
+    /// assert_eq!(DPadDirection::North.as_vector(), (0.0, 1.0));
+    /// assert_eq!(DPadDirection::None.as_vector(), (0.0, 0.0));
+    ///
+    /// let (x, y) = DPadDirection::SouthEast.as_vector();
+    /// assert!((x.hypot(y) - 1.0).abs() < 1e-6);
+    /// ```
+    ///
+    /// [`None`]: DPadDirection::None
+    pub fn as_vector(&self) -> (f32, f32) {
+        use std::f32::consts::FRAC_1_SQRT_2 as D;
+
+        match self {
+            DPadDirection::North => (0.0, 1.0),
+            DPadDirection::NorthEast => (D, D),
+            DPadDirection::East => (1.0, 0.0),
+            DPadDirection::SouthEast => (D, -D),
+            DPadDirection::South => (0.0, -1.0),
+            DPadDirection::SouthWest => (-D, -D),
+            DPadDirection::West => (-1.0, 0.0),
+            DPadDirection::NorthWest => (-D, D),
+            DPadDirection::None => (0.0, 0.0),
+        }
+    }
+}
+
 impl From<u8> for ButtonState {
     fn from(value: u8) -> Self {
         assert!(value < 2, "Out of range for ButtonState");
@@ -505,8 +1642,6 @@ impl From<u8> for MutedState {
 
 impl From<u8> for PowerState {
     fn from(value: u8) -> Self {
-        assert!(value <= 0x0F, "Out of range for PowerState");
-
         match value {
             0x00 => PowerState::Discharging,
             0x01 => PowerState::Charging,
@@ -514,67 +1649,55 @@ impl From<u8> for PowerState {
             0x0A => PowerState::AbnormalVoltage,
             0x0B => PowerState::AbnormalTemperature,
             0x0F => PowerState::ChargingError,
-            _ => unreachable!(),
+            value => PowerState::Unknown(value),
         }
     }
 }
 
-impl From<u8> for DPadDirection {
-    fn from(value: u8) -> Self {
-        assert!(value < 9, "Out of range for DPadDirection");
+impl TryFrom<u8> for DPadDirection {
+    /// The value, if it is not a direction.
+    type Error = u8;
 
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
-            0 => DPadDirection::North,
-            1 => DPadDirection::NorthEast,
-            2 => DPadDirection::East,
-            3 => DPadDirection::SouthEast,
-            4 => DPadDirection::South,
-            5 => DPadDirection::SouthWest,
-            6 => DPadDirection::West,
-            7 => DPadDirection::NorthWest,
-            8 => DPadDirection::None,
-            _ => unreachable!(),
+            0 => Ok(DPadDirection::North),
+            1 => Ok(DPadDirection::NorthEast),
+            2 => Ok(DPadDirection::East),
+            3 => Ok(DPadDirection::SouthEast),
+            4 => Ok(DPadDirection::South),
+            5 => Ok(DPadDirection::SouthWest),
+            6 => Ok(DPadDirection::West),
+            7 => Ok(DPadDirection::NorthWest),
+            8 => Ok(DPadDirection::None),
+            value => Err(value),
         }
     }
 }
+
 impl From<u8> for BackTriggerEffect {
     fn from(value: u8) -> Self {
-        assert!(value < 16, "Out of range for BackTriggerEffect");
-
         match value {
             0 => BackTriggerEffect::Off,
             1 => BackTriggerEffect::Feedback,
             2 => BackTriggerEffect::Weapon,
             3 => BackTriggerEffect::Vibration,
-            _ => unreachable!(),
+            value => BackTriggerEffect::Unknown(value),
         }
     }
 }
 
 impl From<(u8, BackTriggerEffect)> for BackTriggerStatus {
     fn from(value: (u8, BackTriggerEffect)) -> Self {
-        let (value, effect) = value;
-
-        assert!(value < 3, "Out of range for BackTriggerStatus");
-
-        match effect {
-            BackTriggerEffect::Off => BackTriggerStatus::FeedbackNoLoad,
-            BackTriggerEffect::Feedback => match value {
-                0 => BackTriggerStatus::FeedbackNoLoad,
-                1 => BackTriggerStatus::FeedbackLoadApplied,
-                _ => unreachable!(),
-            },
-            BackTriggerEffect::Weapon => match value {
-                0 => BackTriggerStatus::WeaponReady,
-                1 => BackTriggerStatus::WeaponFiring,
-                2 => BackTriggerStatus::WeaponFired,
-                _ => unreachable!(),
-            },
-            BackTriggerEffect::Vibration => match value {
-                0 => BackTriggerStatus::VibrationNotVibrating,
-                1 => BackTriggerStatus::VibrationIsVibrating,
-                _ => unreachable!(),
-            },
+        match value {
+            (_, BackTriggerEffect::Off) => BackTriggerStatus::FeedbackNoLoad,
+            (0, BackTriggerEffect::Feedback) => BackTriggerStatus::FeedbackNoLoad,
+            (1, BackTriggerEffect::Feedback) => BackTriggerStatus::FeedbackLoadApplied,
+            (0, BackTriggerEffect::Weapon) => BackTriggerStatus::WeaponReady,
+            (1, BackTriggerEffect::Weapon) => BackTriggerStatus::WeaponFiring,
+            (2, BackTriggerEffect::Weapon) => BackTriggerStatus::WeaponFired,
+            (0, BackTriggerEffect::Vibration) => BackTriggerStatus::VibrationNotVibrating,
+            (1, BackTriggerEffect::Vibration) => BackTriggerStatus::VibrationIsVibrating,
+            (value, _) => BackTriggerStatus::Unknown(value),
         }
     }
 }
@@ -0,0 +1,193 @@
+//! The mock device module.
+//!
+//! This module provides a fake device that can be used in place of a real controller. It is mostly
+//! useful for testing code that interacts with a [`DualSense`] without any hardware attached.
+//!
+//! [`DualSense`]: struct@crate::DualSense
+
+use crate::hidapi::{DeviceString, Error, HidDevice};
+use crate::Mode;
+
+use libc::c_int;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A fake device that replays a queue of canned reports.
+///
+/// Every read from the device pops the oldest report in the queue. When the queue is empty, a
+/// _non-blocking_ read (or a read with a timeout) returns no data, while a _blocking_ read returns
+/// an error, since no report will ever arrive.
+///
+/// Clones of a mock device share the same queue, so a clone can be kept around to push new
+/// reports after the device was handed to a [`DualSense`]:
+///
+/// ```rust
+/// # #[cfg(feature = "mock")]
+/// # {
+/// use duplosentido::{mock::MockDevice, DualSense};
+///
+/// let device = MockDevice::new();
+/// let ds = DualSense::mock(device.clone()).unwrap();
+///
+/// let mut report = [0_u8; 64];
+/// report[0] = 0x01;
+/// report[8] = 0x08 | 0b0010_0000; // Cross pressed.
+/// device.push_report(&report);
+///
+/// assert_eq!(ds.update().unwrap(), 64);
+/// assert!(ds.state().cross().is_pressed());
+/// assert!(ds.update().is_err());
+/// # }
+/// ```
+///
+/// [`DualSense`]: struct@crate::DualSense
+#[derive(Debug, Clone, Default)]
+pub struct MockDevice {
+    reports: Arc<Mutex<VecDeque<Vec<u8>>>>,
+    mode: Arc<Mutex<Option<Mode>>>,
+    disconnected: Arc<AtomicBool>,
+    feature_reports: Arc<Mutex<HashMap<u8, Vec<u8>>>>,
+    written: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl MockDevice {
+    /// Create a mock device with an empty report queue.
+    pub fn new() -> Self {
+        MockDevice::default()
+    }
+
+    /// Push a report to the end of the queue.
+    ///
+    /// The report is returned as is, so it can be of any length or type. This makes it possible to
+    /// also test how unsupported reports are handled.
+    pub fn push_report(&self, report: &[u8]) {
+        self.reports.lock().unwrap().push_back(report.to_vec());
+    }
+
+    /// Set the feature report returned when its ID is requested.
+    ///
+    /// The ID of the report is its first byte. Setting a report with the same ID replaces the
+    /// previous one, and requesting a report that was never set returns an error.
+    pub fn set_feature_report(&self, report: &[u8]) {
+        if let Some(&id) = report.first() {
+            self.feature_reports
+                .lock()
+                .unwrap()
+                .insert(id, report.to_vec());
+        }
+    }
+
+    /// Get every report written to the device, the oldest one first.
+    pub fn written(&self) -> Vec<Vec<u8>> {
+        self.written.lock().unwrap().clone()
+    }
+
+    /// Simulate the device being disconnected.
+    ///
+    /// Every following read fails with a disconnection error, even if there are reports left in
+    /// the queue:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, DualSense, Error};
+    ///
+    /// let device = MockDevice::new();
+    /// let ds = DualSense::mock(device.clone()).unwrap();
+    ///
+    /// device.disconnect();
+    ///
+    /// assert!(matches!(ds.update(), Err(Error::Disconnected)));
+    /// # }
+    /// ```
+    pub fn disconnect(&self) {
+        self.disconnected.store(true, Ordering::Relaxed);
+    }
+
+    /// Get the number of reports still in the queue.
+    pub fn pending(&self) -> usize {
+        self.reports.lock().unwrap().len()
+    }
+
+    /// Pop the next report into `buf`, returning `None` if the queue is empty.
+    fn pop(&self, buf: &mut [u8]) -> Option<usize> {
+        let report = self.reports.lock().unwrap().pop_front()?;
+        let bytes = report.len().min(buf.len());
+        buf[..bytes].copy_from_slice(&report[..bytes]);
+
+        Some(bytes)
+    }
+}
+
+impl HidDevice for MockDevice {
+    fn set_mode(&self, mode: Mode) -> Result<(), Error> {
+        self.mode.lock().unwrap().replace(mode);
+
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        if self.disconnected.load(Ordering::Relaxed) {
+            return Err(Error::Disconnected);
+        }
+
+        match self.pop(buf) {
+            Some(bytes) => Ok(bytes),
+            None if *self.mode.lock().unwrap() == Some(Mode::NonBlocking) => Ok(0),
+            None => Err(Error::Read),
+        }
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], milliseconds: c_int) -> Result<usize, Error> {
+        if self.disconnected.load(Ordering::Relaxed) {
+            return Err(Error::Disconnected);
+        }
+
+        match self.pop(buf) {
+            Some(bytes) => Ok(bytes),
+            None if milliseconds >= 0 => Ok(0),
+            None => Err(Error::Read),
+        }
+    }
+
+    fn write(&self, buf: &[u8]) -> Result<usize, Error> {
+        if self.disconnected.load(Ordering::Relaxed) {
+            return Err(Error::Write);
+        }
+
+        self.written.lock().unwrap().push(buf.to_vec());
+
+        Ok(buf.len())
+    }
+
+    fn get_feature_report(&self, buf: &mut [u8]) -> Result<usize, Error> {
+        let reports = self.feature_reports.lock().unwrap();
+        let report = reports.get(&buf[0]).ok_or(Error::FeatureReport)?;
+        let bytes = report.len().min(buf.len());
+        buf[..bytes].copy_from_slice(&report[..bytes]);
+
+        Ok(bytes)
+    }
+
+    /// Get the strings of a standard DualSense controller, without a serial number.
+    fn get_string(&self, string: DeviceString) -> Result<String, Error> {
+        let string = match string {
+            DeviceString::Manufacturer => "Sony Interactive Entertainment",
+            DeviceString::Product => "DualSense Wireless Controller",
+            DeviceString::SerialNumber => "",
+        };
+
+        Ok(string.to_string())
+    }
+
+    /// Reopen the device, clearing a simulated disconnection.
+    ///
+    /// The reopened device shares the same queue, so reports pushed before and after reopening
+    /// are still read.
+    fn reopen(&self) -> Result<Box<dyn HidDevice>, Error> {
+        self.disconnected.store(false, Ordering::Relaxed);
+
+        Ok(Box::new(self.clone()))
+    }
+}
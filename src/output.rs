@@ -0,0 +1,535 @@
+//! The controller output module.
+//!
+//! This module contains the types used to drive the outputs of the controller, such as its
+//! rumble motors and its lightbar. Outputs are sent to the controller through an output report,
+//! which is written by the methods of [`DualSense`]. To change multiple outputs at once, see
+//! [`Outputs`].
+//!
+//! [`DualSense`]: struct@crate::DualSense
+//! [`Outputs`]: struct@crate::output::Outputs
+
+use crate::{Error, Result};
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+/// The outputs of the controller.
+///
+/// Every field set to `Some` is changed, while the ones set to `None` are left as they are. All of
+/// the outputs are sent in a single report by [`DualSense::set_outputs`], which avoids the flicker
+/// caused by changing them one at a time:
+///
+/// ```rust,no_run
+/// use duplosentido::output::{Color, Outputs, TriggerEffect};
+/// use duplosentido::DualSense;
+///
+/// let ds = DualSense::bind().expect("At least one controller should be connected");
+///
+/// ds.set_outputs(&Outputs {
+///     rumble: Some((0, 128)),
+///     lightbar: Some(Color::RED),
+///     player_leds: Some(0b00100),
+///     right_trigger: Some(TriggerEffect::Weapon {
+///         start: 2,
+///         end: 6,
+///         strength: 8,
+///     }),
+///     ..Outputs::default()
+/// })
+/// .unwrap();
+/// ```
+///
+/// [`DualSense::set_outputs`]: fn@crate::DualSense::set_outputs
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outputs {
+    /// The intensity of the left and right rumble motors, respectively.
+    pub rumble: Option<(u8, u8)>,
+    /// The color of the lightbar.
+    pub lightbar: Option<Color>,
+    /// The player LEDs below the touchpad, one bit per LED from left to right (`0b10000` is the
+    /// leftmost one). Only the 5 lowest bits are used.
+    pub player_leds: Option<u8>,
+    /// If the LED of the mute button is on.
+    pub mic_led: Option<bool>,
+    /// If the microphone is muted.
+    pub mic_muted: Option<bool>,
+    /// The effect of the left back trigger (L2).
+    pub left_trigger: Option<TriggerEffect>,
+    /// The effect of the right back trigger (R2).
+    pub right_trigger: Option<TriggerEffect>,
+}
+
+impl Outputs {
+    /// Update the outputs with the ones set in `other`, keeping the others as they are.
+    pub(crate) fn merge(&mut self, other: &Outputs) {
+        *self = Outputs {
+            rumble: other.rumble.or(self.rumble),
+            lightbar: other.lightbar.or(self.lightbar),
+            player_leds: other.player_leds.or(self.player_leds),
+            mic_led: other.mic_led.or(self.mic_led),
+            mic_muted: other.mic_muted.or(self.mic_muted),
+            left_trigger: other.left_trigger.or(self.left_trigger),
+            right_trigger: other.right_trigger.or(self.right_trigger),
+        };
+    }
+}
+
+/// A color of the lightbar.
+///
+/// ```rust
+/// use duplosentido::output::Color;
+///
+/// assert_eq!(Color::from_hex(0xFF8800), Color { r: 255, g: 136, b: 0 });
+/// assert_eq!(Color::from_hex(0x0000FF), Color::BLUE);
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    /// The red component.
+    pub r: u8,
+    /// The green component.
+    pub g: u8,
+    /// The blue component.
+    pub b: u8,
+}
+
+impl Color {
+    /// The lightbar turned off.
+    pub const OFF: Color = Color::new(0, 0, 0);
+    /// Red.
+    pub const RED: Color = Color::new(255, 0, 0);
+    /// Green.
+    pub const GREEN: Color = Color::new(0, 255, 0);
+    /// Blue.
+    pub const BLUE: Color = Color::new(0, 0, 255);
+    /// Yellow.
+    pub const YELLOW: Color = Color::new(255, 255, 0);
+    /// Cyan.
+    pub const CYAN: Color = Color::new(0, 255, 255);
+    /// Magenta.
+    pub const MAGENTA: Color = Color::new(255, 0, 255);
+    /// White.
+    pub const WHITE: Color = Color::new(255, 255, 255);
+
+    /// Create a color from its red, green and blue components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b }
+    }
+
+    /// Create a color from its `0xRRGGBB` hexadecimal representation.
+    ///
+    /// The highest byte of `hex` is ignored.
+    pub const fn from_hex(hex: u32) -> Self {
+        let [_, r, g, b] = hex.to_be_bytes();
+
+        Color { r, g, b }
+    }
+}
+
+/// The player LEDs of each player number, as used by the console.
+///
+/// The pattern of player `n` is at index `n - 1`, so player 1 lights only the center LED and
+/// player 5 lights all of them. See [`set_player_leds`] for more information.
+///
+/// [`set_player_leds`]: fn@crate::DualSense::set_player_leds
+pub const PLAYER_LEDS: [u8; 5] = [0b00100, 0b01010, 0b10101, 0b11011, 0b11111];
+
+/// A side of the controller, used to select one of the adaptive back triggers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriggerSide {
+    /// The left back trigger (L2).
+    Left,
+    /// The right back trigger (R2).
+    Right,
+}
+
+/// An effect of an adaptive back trigger.
+///
+/// The trigger travel is divided in 10 zones, from `0` (released) to `9` (fully pressed).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriggerEffect {
+    /// No effect, the trigger moves freely.
+    Off,
+    /// The trigger resists being pressed from `position` onwards.
+    Feedback {
+        /// The zone where the resistance starts, from `0` to `9`.
+        position: u8,
+        /// The strength of the resistance, from `1` to `8`.
+        strength: u8,
+    },
+    /// The trigger resists being pressed from `start` to `end`, and then snaps like a gun trigger.
+    Weapon {
+        /// The zone where the resistance starts, from `2` to `7`.
+        start: u8,
+        /// The zone where the trigger snaps, from `start + 1` to `8`.
+        end: u8,
+        /// The strength of the resistance, from `1` to `8`.
+        strength: u8,
+    },
+    /// The trigger vibrates from `position` onwards.
+    Vibration {
+        /// The zone where the vibration starts, from `0` to `9`.
+        position: u8,
+        /// The amplitude of the vibration, from `1` to `8`.
+        amplitude: u8,
+        /// The frequency of the vibration, in hertz.
+        frequency: u8,
+    },
+}
+
+impl TriggerEffect {
+    /// Check the parameters of the effect, clamping the ones out of range.
+    ///
+    /// See [`DualSense::set_trigger_effect`] for the accepted ranges.
+    ///
+    /// [`DualSense::set_trigger_effect`]: fn@crate::DualSense::set_trigger_effect
+    pub(crate) fn validate(self) -> Result<TriggerEffect> {
+        let strength = |strength: u8, message| match strength {
+            0 => Err(Error::InvalidTriggerEffect(message)),
+            strength => Ok(strength.min(8)),
+        };
+
+        let effect = match self {
+            TriggerEffect::Off => TriggerEffect::Off,
+            TriggerEffect::Feedback {
+                position,
+                strength: force,
+            } => TriggerEffect::Feedback {
+                position: position.min(9),
+                strength: strength(force, "the feedback strength must be at least 1")?,
+            },
+            TriggerEffect::Weapon {
+                start,
+                end,
+                strength: force,
+            } => {
+                let start = start.clamp(2, 7);
+                if end <= start {
+                    return Err(Error::InvalidTriggerEffect(
+                        "the weapon must snap after it starts",
+                    ));
+                }
+
+                TriggerEffect::Weapon {
+                    start,
+                    end: end.min(8),
+                    strength: strength(force, "the weapon strength must be at least 1")?,
+                }
+            }
+            TriggerEffect::Vibration {
+                position,
+                amplitude,
+                frequency,
+            } => {
+                if frequency == 0 {
+                    return Err(Error::InvalidTriggerEffect(
+                        "the vibration frequency must be at least 1",
+                    ));
+                }
+
+                TriggerEffect::Vibration {
+                    position: position.min(9),
+                    amplitude: strength(amplitude, "the vibration amplitude must be at least 1")?,
+                    frequency,
+                }
+            }
+        };
+
+        Ok(effect)
+    }
+
+    /// Encode the effect in the 11 bytes used by the output report.
+    fn encode(&self) -> [u8; 11] {
+        // Every zone from `position` onwards is active, with a 3-bit `strength` each.
+        let zones = |position: u8, strength: u8| {
+            let strength = (strength.saturating_sub(1) & 0x07) as u32;
+            let (mut active, mut strengths) = (0_u16, 0_u32);
+            for zone in position..10 {
+                active |= 1 << zone;
+                strengths |= strength << (3 * zone);
+            }
+
+            (active.to_le_bytes(), strengths.to_le_bytes())
+        };
+
+        let mut effect = [0_u8; 11];
+        match *self {
+            TriggerEffect::Off => effect[0] = 0x05,
+            TriggerEffect::Feedback { position, strength } => {
+                let (active, strengths) = zones(position, strength);
+                effect[0] = 0x21;
+                effect[1..3].copy_from_slice(&active);
+                effect[3..7].copy_from_slice(&strengths);
+            }
+            TriggerEffect::Weapon {
+                start,
+                end,
+                strength,
+            } => {
+                let zones = (1_u16 << start.min(9)) | (1_u16 << end.min(9));
+                effect[0] = 0x25;
+                effect[1..3].copy_from_slice(&zones.to_le_bytes());
+                effect[3] = strength.saturating_sub(1) & 0x07;
+            }
+            TriggerEffect::Vibration {
+                position,
+                amplitude,
+                frequency,
+            } => {
+                let (active, amplitudes) = zones(position, amplitude);
+                effect[0] = 0x26;
+                effect[1..3].copy_from_slice(&active);
+                effect[3..7].copy_from_slice(&amplitudes);
+                effect[9] = frequency;
+            }
+        }
+
+        effect
+    }
+}
+
+/// A step of a rumble pattern.
+///
+/// See [`DualSense::play_rumble_pattern`] for more information.
+///
+/// [`DualSense::play_rumble_pattern`]: fn@crate::DualSense::play_rumble_pattern
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RumbleStep {
+    /// The intensity of the left (low frequency) motor.
+    pub left: u8,
+    /// The intensity of the right (high frequency) motor.
+    pub right: u8,
+    /// For how long the step lasts.
+    pub duration: Duration,
+}
+
+/// A timed sequence of values being played, such as a rumble pattern or a lightbar fade.
+#[derive(Debug, Clone)]
+pub(crate) struct Sequence<T> {
+    /// The values and for how long each one lasts.
+    steps: Vec<(T, Duration)>,
+    /// The value applied once every step was played.
+    end: T,
+    /// The index of the current step.
+    current: usize,
+    /// When the current step started.
+    started: Instant,
+}
+
+impl<T: Copy> Sequence<T> {
+    /// Start playing the `steps`, returning the sequence and the first value.
+    pub(crate) fn start(steps: Vec<(T, Duration)>, end: T) -> Option<(Sequence<T>, T)> {
+        let (first, _) = *steps.first()?;
+        let sequence = Sequence {
+            steps,
+            end,
+            current: 0,
+            started: Instant::now(),
+        };
+
+        Some((sequence, first))
+    }
+
+    /// Advance the sequence to the step that should be playing now.
+    ///
+    /// Returns the new value if it changed, or `None` if it is still the same. Once every step was
+    /// played, the `end` value is returned and the sequence is finished.
+    pub(crate) fn advance(&mut self) -> Option<T> {
+        let now = Instant::now();
+        let mut changed = false;
+
+        while self.current < self.steps.len()
+            && now.duration_since(self.started) >= self.steps[self.current].1
+        {
+            self.started += self.steps[self.current].1;
+            self.current += 1;
+            changed = true;
+        }
+
+        match self.steps.get(self.current) {
+            Some(&(value, _)) if changed => Some(value),
+            Some(_) => None,
+            None => Some(self.end),
+        }
+    }
+
+    /// Return `true` if every step was played and `false` otherwise.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.current >= self.steps.len()
+    }
+}
+
+/// Advance the sequence in `slot`, removing it once it is finished.
+///
+/// Returns the value to apply, if it changed.
+pub(crate) fn advance_sequence<T: Copy>(slot: &RefCell<Option<Sequence<T>>>) -> Option<T> {
+    let mut sequence = slot.borrow_mut();
+    let value = sequence.as_mut().and_then(Sequence::advance);
+
+    if sequence.as_ref().is_some_and(Sequence::is_finished) {
+        *sequence = None;
+    }
+
+    value
+}
+
+/// Interpolate `steps` colors from `from` to `to`, each one lasting for an equal part of
+/// `duration`.
+pub(crate) fn fade(
+    from: Color,
+    to: Color,
+    duration: Duration,
+    steps: u32,
+) -> Vec<(Color, Duration)> {
+    let steps = steps.max(1);
+    let lerp =
+        |from: u8, to: u8, t: f32| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+
+    (0..steps)
+        .map(|step| {
+            let t = step as f32 / steps as f32;
+            let color = Color {
+                r: lerp(from.r, to.r, t),
+                g: lerp(from.g, to.g, t),
+                b: lerp(from.b, to.b, t),
+            };
+
+            (color, duration / steps)
+        })
+        .collect()
+}
+
+/// The first flag byte of the output report, selecting which outputs are changed.
+const FLAG0: usize = 1;
+
+/// The second flag byte of the output report, selecting which outputs are changed.
+const FLAG1: usize = 2;
+
+/// Enable the rumble emulation of the DualShock 4, which drives the motors from the rumble bytes.
+pub(crate) const FLAG0_COMPATIBLE_VIBRATION: u8 = 0x01;
+
+/// Select the haptics, which must be set along with [`FLAG0_COMPATIBLE_VIBRATION`].
+pub(crate) const FLAG0_HAPTICS_SELECT: u8 = 0x02;
+
+/// Change the effect of the right back trigger (R2).
+pub(crate) const FLAG0_RIGHT_TRIGGER: u8 = 0x04;
+
+/// Change the effect of the left back trigger (L2).
+pub(crate) const FLAG0_LEFT_TRIGGER: u8 = 0x08;
+
+/// Change the LED of the mute button.
+pub(crate) const FLAG1_MIC_LED: u8 = 0x01;
+
+/// Change the power save control, which holds the microphone mute.
+pub(crate) const FLAG1_POWER_SAVE: u8 = 0x02;
+
+/// Change the color of the lightbar.
+pub(crate) const FLAG1_LIGHTBAR: u8 = 0x04;
+
+/// Change the player LEDs.
+pub(crate) const FLAG1_PLAYER_LED: u8 = 0x10;
+
+/// Mute the microphone, in the power save control byte.
+pub(crate) const POWER_SAVE_MUTE_MIC: u8 = 0x10;
+
+/// A raw output report to a DualSense controller using a USB connection.
+///
+/// Every output has a flag in one of the two flag bytes, which must be set for the controller to
+/// apply it. The flags of the outputs not set are left cleared, so they are not changed.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct OutputReport([u8; 48]);
+
+impl OutputReport {
+    /// Create an output report that does not change any output.
+    pub(crate) fn new() -> Self {
+        let mut report = [0_u8; 48];
+        report[0] = 0x02;
+
+        OutputReport(report)
+    }
+
+    /// Set the intensity of the rumble motors.
+    pub(crate) fn set_rumble(&mut self, left: u8, right: u8) {
+        self.0[FLAG0] |= FLAG0_COMPATIBLE_VIBRATION | FLAG0_HAPTICS_SELECT;
+        self.0[3] = right;
+        self.0[4] = left;
+    }
+
+    /// Set the color of the lightbar.
+    pub(crate) fn set_lightbar(&mut self, color: Color) {
+        self.0[FLAG1] |= FLAG1_LIGHTBAR;
+        self.0[45..48].copy_from_slice(&[color.r, color.g, color.b]);
+    }
+
+    /// Set the player LEDs.
+    pub(crate) fn set_player_leds(&mut self, leds: u8) {
+        self.0[FLAG1] |= FLAG1_PLAYER_LED;
+        self.0[44] = leds & 0b0001_1111;
+    }
+
+    /// Turn the LED of the mute button on or off.
+    pub(crate) fn set_mic_led(&mut self, on: bool) {
+        self.0[FLAG1] |= FLAG1_MIC_LED;
+        self.0[9] = on as u8;
+    }
+
+    /// Mute or unmute the microphone.
+    pub(crate) fn set_mic_muted(&mut self, muted: bool) {
+        self.0[FLAG1] |= FLAG1_POWER_SAVE;
+        if muted {
+            self.0[10] |= POWER_SAVE_MUTE_MIC;
+        } else {
+            self.0[10] &= !POWER_SAVE_MUTE_MIC;
+        }
+    }
+
+    /// Set the effect of the left back trigger.
+    pub(crate) fn set_left_trigger(&mut self, effect: &TriggerEffect) {
+        self.0[FLAG0] |= FLAG0_LEFT_TRIGGER;
+        self.0[22..33].copy_from_slice(&effect.encode());
+    }
+
+    /// Set the effect of the right back trigger.
+    pub(crate) fn set_right_trigger(&mut self, effect: &TriggerEffect) {
+        self.0[FLAG0] |= FLAG0_RIGHT_TRIGGER;
+        self.0[11..22].copy_from_slice(&effect.encode());
+    }
+
+    pub(crate) fn as_array(&self) -> &[u8; 48] {
+        &self.0
+    }
+}
+
+impl From<&Outputs> for OutputReport {
+    fn from(outputs: &Outputs) -> Self {
+        let mut report = OutputReport::new();
+
+        if let Some((left, right)) = outputs.rumble {
+            report.set_rumble(left, right);
+        }
+        if let Some(color) = outputs.lightbar {
+            report.set_lightbar(color);
+        }
+        if let Some(leds) = outputs.player_leds {
+            report.set_player_leds(leds);
+        }
+        if let Some(on) = outputs.mic_led {
+            report.set_mic_led(on);
+        }
+        if let Some(muted) = outputs.mic_muted {
+            report.set_mic_muted(muted);
+        }
+        if let Some(effect) = &outputs.left_trigger {
+            report.set_left_trigger(effect);
+        }
+        if let Some(effect) = &outputs.right_trigger {
+            report.set_right_trigger(effect);
+        }
+
+        report
+    }
+}
@@ -0,0 +1,104 @@
+//! The background polling module.
+//!
+//! This module makes it possible to poll the controller in a dedicated thread, decoupling the
+//! input latency from the rate in which the state is consumed (e.g. the frame rate of a game).
+
+use crate::{DualSense, DualSenseState, Error, Result};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// How long the polling thread waits for a report before checking if it should stop.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A handle to a controller being polled in a background thread.
+///
+/// The handle is created by [`DualSense::spawn_poller`]. While it is alive, the thread keeps
+/// updating the controller and publishing its latest state, which can be read at any time with
+/// [`latest`]. Dropping the handle stops the thread, but calling [`stop`] also returns the
+/// controller back.
+///
+/// [`DualSense::spawn_poller`]: fn@crate::DualSense::spawn_poller
+/// [`latest`]: fn@Self::latest
+/// [`stop`]: fn@Self::stop
+pub struct PollHandle {
+    latest: Arc<Mutex<DualSenseState>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<Result<DualSense>>>,
+}
+
+impl PollHandle {
+    /// Move the controller to a new thread and start polling it.
+    pub(crate) fn spawn(controller: DualSense) -> PollHandle {
+        let latest = Arc::new(Mutex::new(controller.state()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let latest = Arc::clone(&latest);
+            let stop = Arc::clone(&stop);
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    match controller.update_timeout(STOP_CHECK_INTERVAL) {
+                        Ok(0) | Err(Error::UnsupportedReport { .. }) => continue,
+                        Ok(_) => *latest.lock().unwrap() = controller.state(),
+                        Err(e) => return Err(e),
+                    }
+                }
+
+                Ok(controller)
+            })
+        };
+
+        PollHandle {
+            latest,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Get the latest controller state read by the polling thread.
+    pub fn latest(&self) -> DualSenseState {
+        *self.latest.lock().unwrap()
+    }
+
+    /// Return `true` if the polling thread is still running and `false` otherwise.
+    ///
+    /// The thread stops by itself if updating the controller fails (e.g. if it was disconnected).
+    /// The error can then be retrieved with [`stop`].
+    ///
+    /// [`stop`]: fn@Self::stop
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    /// Stop the polling thread, returning the controller back.
+    ///
+    /// If the thread stopped by itself due to an error, the error is returned instead. This method
+    /// can block for a short time (up to 100ms) while the thread finishes its current update.
+    pub fn stop(mut self) -> Result<DualSense> {
+        self.join()
+    }
+
+    /// Signal the polling thread to stop and wait for it.
+    fn join(&mut self) -> Result<DualSense> {
+        self.stop.store(true, Ordering::Relaxed);
+
+        let thread = self.thread.take().expect("The thread is only joined once");
+        match thread.join() {
+            Ok(result) => result,
+            Err(panic) => std::panic::resume_unwind(panic),
+        }
+    }
+}
+
+impl Drop for PollHandle {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            // The controller (or the error) is not needed anymore, so it can be dropped here.
+            let _ = self.join();
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! The input recording module.
+//!
+//! This module makes it possible to record the reports sent by a controller, so a real input
+//! session can be captured and inspected later (e.g. to reproduce a bug). Recordings can also be
+//! replayed with a [`Replayer`], driving a [`DualSense`] without any hardware.
+//!
+//! # Format
+//! A recording is a sequence of fixed-size records, without any header. Each record is
+//! [`RECORD_SIZE`] bytes long and has the following layout:
+//!
+//! | Bytes     | Content                                                                        |
+//! |-----------|--------------------------------------------------------------------------------|
+//! | `0..8`    | Time since the recording started, in microseconds (`u64`, little-endian).      |
+//! | `8..72`   | The raw 64-byte USB input report, as returned by [`DualSense::raw_report`].    |
+//!
+//! [`DualSense::raw_report`]: fn@crate::DualSense::raw_report
+//! [`Replayer`]: struct@crate::recording::Replayer
+//! [`DualSense`]: struct@crate::DualSense
+
+use crate::hidapi::{self, DeviceString, HidDevice};
+use crate::{DualSense, Mode};
+
+use libc::c_int;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The size of a single record, in bytes.
+pub const RECORD_SIZE: usize = 8 + 64;
+
+/// A controller whose reports are recorded as they are read.
+///
+/// The recorder is created by [`DualSense::record`]. Every successful [`update`] appends the read
+/// report to the writer, following the [format] described in the module documentation. Writes are
+/// not buffered, so a [`BufWriter`] should be used when recording to a file:
+///
+/// ```rust,no_run
+/// use duplosentido::DualSense;
+/// use std::fs::File;
+/// use std::io::BufWriter;
+///
+/// let ds = DualSense::bind().expect("At least one controller should be connected");
+/// let file = File::create("session.bin").unwrap();
+/// let mut recorder = ds.record(BufWriter::new(file));
+///
+/// loop {
+///     recorder.update().unwrap();
+///     if recorder.controller().state().home_menu().is_pressed() {
+///         break;
+///     }
+/// }
+///
+/// let (ds, mut writer) = recorder.into_inner();
+/// ```
+///
+/// [`DualSense::record`]: fn@crate::DualSense::record
+/// [`update`]: fn@Self::update
+/// [format]: mod@crate::recording#format
+/// [`BufWriter`]: std::io::BufWriter
+pub struct Recorder<W: Write> {
+    controller: DualSense,
+    writer: W,
+    start: Instant,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Start recording the controller reports to `writer`.
+    pub(crate) fn new(controller: DualSense, writer: W) -> Self {
+        Recorder {
+            controller,
+            writer,
+            start: Instant::now(),
+        }
+    }
+
+    /// Update the controller state, recording the read report.
+    ///
+    /// This method behaves exactly like [`DualSense::update`]. Nothing is recorded if no report
+    /// was read (e.g. in _non-blocking_ mode) or if the update failed.
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mock")]
+    /// # {
+    /// use duplosentido::{mock::MockDevice, recording::RECORD_SIZE, DualSense};
+    ///
+    /// let device = MockDevice::new();
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// device.push_report(&report);
+    ///
+    /// let mut recorder = DualSense::mock(device).unwrap().record(Vec::new());
+    /// recorder.update().unwrap();
+    ///
+    /// let (_, recording) = recorder.into_inner();
+    /// assert_eq!(recording.len(), RECORD_SIZE);
+    /// assert_eq!(recording[8..], report);
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// Besides the errors returned by [`DualSense::update`], [`Error::Io`] is returned if writing
+    /// the record fails. In this case, the controller state is still updated.
+    ///
+    /// [`DualSense::update`]: fn@crate::DualSense::update
+    /// [`Error::Io`]: crate::Error::Io
+    pub fn update(&mut self) -> crate::Result<usize> {
+        let bytes = self.controller.update()?;
+        if bytes == 0 {
+            return Ok(bytes);
+        }
+
+        let elapsed = self.start.elapsed().as_micros() as u64;
+        let mut record = [0_u8; RECORD_SIZE];
+        record[..8].copy_from_slice(&elapsed.to_le_bytes());
+        record[8..].copy_from_slice(&self.controller.raw_report());
+        self.writer.write_all(&record)?;
+
+        Ok(bytes)
+    }
+
+    /// Get the controller being recorded.
+    pub fn controller(&self) -> &DualSense {
+        &self.controller
+    }
+
+    /// Stop recording, returning the controller and the writer back.
+    pub fn into_inner(self) -> (DualSense, W) {
+        (self.controller, self.writer)
+    }
+}
+
+/// A device that replays a recording of the controller reports.
+///
+/// A replayer is binded like a real controller, with [`DualSense::replay`], so code that uses a
+/// [`DualSense`] can be driven by a recording instead. By default, the reports are read at the
+/// same pace they were recorded, respecting the current [`Mode`]. With fast-forward enabled, every
+/// report is read immediately, which is useful for deterministic tests.
+///
+/// When there are no reports left, the device behaves as if the controller was disconnected.
+///
+/// ```rust
+/// use duplosentido::recording::{Replayer, RECORD_SIZE};
+/// use duplosentido::{DualSense, Error};
+///
+/// let mut recording = [0_u8; RECORD_SIZE];
+/// recording[8] = 0x01;
+/// recording[16] = 0x08 | 0b0010_0000; // Cross pressed.
+///
+/// let replayer = Replayer::new(&recording[..]).unwrap().with_fast_forward(true);
+/// let ds = DualSense::replay(replayer).unwrap();
+///
+/// assert_eq!(ds.update().unwrap(), 64);
+/// assert!(ds.state().cross().is_pressed());
+/// assert!(matches!(ds.update(), Err(Error::Disconnected)));
+/// ```
+///
+/// [`DualSense::replay`]: fn@crate::DualSense::replay
+/// [`DualSense`]: struct@crate::DualSense
+/// [`Mode`]: enum@crate::Mode
+#[derive(Debug)]
+pub struct Replayer {
+    records: RefCell<VecDeque<(Duration, [u8; 64])>>,
+    fast_forward: bool,
+    mode: Cell<Mode>,
+    // The instant the first report was read, which the timestamps of the records are relative to.
+    start: Cell<Option<Instant>>,
+}
+
+impl Replayer {
+    /// Create a replayer from a recording read from `reader`.
+    ///
+    /// The whole recording is read at once, so the reader is not needed after this call.
+    ///
+    /// # Errors
+    /// If reading fails, or if the recording ends in the middle of a record, [`Error::Io`] is
+    /// returned.
+    ///
+    /// [`Error::Io`]: crate::Error::Io
+    pub fn new(mut reader: impl Read) -> crate::Result<Self> {
+        let mut recording = Vec::new();
+        reader.read_to_end(&mut recording)?;
+
+        if recording.len() % RECORD_SIZE != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Truncated record").into());
+        }
+
+        let records = recording
+            .chunks_exact(RECORD_SIZE)
+            .map(|record| {
+                let elapsed = u64::from_le_bytes(record[..8].try_into().unwrap());
+                let report = record[8..].try_into().unwrap();
+
+                (Duration::from_micros(elapsed), report)
+            })
+            .collect();
+
+        Ok(Replayer {
+            records: RefCell::new(records),
+            fast_forward: false,
+            mode: Cell::new(Mode::Blocking),
+            start: Cell::new(None),
+        })
+    }
+
+    /// Enable or disable fast-forward.
+    ///
+    /// With fast-forward enabled, the timestamps of the recording are ignored and every report is
+    /// read as soon as it is requested.
+    pub fn with_fast_forward(mut self, enabled: bool) -> Self {
+        self.fast_forward = enabled;
+        self
+    }
+
+    /// Get the number of reports not yet replayed.
+    pub fn remaining(&self) -> usize {
+        self.records.borrow().len()
+    }
+
+    /// Pop the next report into `buf` after waiting at most `timeout` for it to be due.
+    ///
+    /// A `timeout` of `None` waits for as long as needed.
+    fn next(&self, buf: &mut [u8], timeout: Option<Duration>) -> Result<usize, hidapi::Error> {
+        let mut records = self.records.borrow_mut();
+        let Some(&(elapsed, report)) = records.front() else {
+            return Err(hidapi::Error::Disconnected);
+        };
+
+        if !self.fast_forward {
+            let start = self.start.get().unwrap_or_else(Instant::now);
+            self.start.set(Some(start));
+
+            let wait = (start + elapsed).saturating_duration_since(Instant::now());
+            match timeout {
+                Some(timeout) if wait > timeout => {
+                    thread::sleep(timeout);
+                    return Ok(0);
+                }
+                _ => thread::sleep(wait),
+            }
+        }
+
+        records.pop_front();
+        let bytes = report.len().min(buf.len());
+        buf[..bytes].copy_from_slice(&report[..bytes]);
+
+        Ok(bytes)
+    }
+}
+
+impl HidDevice for Replayer {
+    fn set_mode(&self, mode: Mode) -> Result<(), hidapi::Error> {
+        self.mode.set(mode);
+
+        Ok(())
+    }
+
+    fn read(&self, buf: &mut [u8]) -> Result<usize, hidapi::Error> {
+        match self.mode.get() {
+            Mode::Blocking => self.next(buf, None),
+            Mode::NonBlocking => self.next(buf, Some(Duration::ZERO)),
+        }
+    }
+
+    fn read_timeout(&self, buf: &mut [u8], milliseconds: c_int) -> Result<usize, hidapi::Error> {
+        let timeout = u64::try_from(milliseconds).ok().map(Duration::from_millis);
+
+        self.next(buf, timeout)
+    }
+
+    /// Discard the written report, since there is no controller to receive it.
+    fn write(&self, buf: &[u8]) -> Result<usize, hidapi::Error> {
+        Ok(buf.len())
+    }
+
+    fn get_feature_report(&self, _buf: &mut [u8]) -> Result<usize, hidapi::Error> {
+        Err(hidapi::Error::FeatureReport)
+    }
+
+    fn get_string(&self, _string: DeviceString) -> Result<String, hidapi::Error> {
+        Err(hidapi::Error::DeviceString)
+    }
+
+    fn reopen(&self) -> Result<Box<dyn HidDevice>, hidapi::Error> {
+        Err(hidapi::Error::Open)
+    }
+}
@@ -0,0 +1,392 @@
+//! The button tracking module.
+//!
+//! A [`DualSenseState`] is a snapshot of the controller, so it has no notion of time. This module
+//! keeps track of the states over time, making it possible to ask temporal questions such as for
+//! how long a button has been held.
+//!
+//! [`DualSenseState`]: struct@crate::DualSenseState
+
+use crate::mappings::{Button, MicrophoneState, MutedState, PluggedState};
+use crate::DualSenseState;
+
+use std::time::Duration;
+
+/// The default maximum duration of a tap.
+const DEFAULT_TAP_THRESHOLD: Duration = Duration::from_millis(200);
+
+/// The tracking data of a single button.
+#[derive(Debug, Copy, Clone, Default)]
+struct TrackedButton {
+    /// When the button was pressed, if it is currently pressed.
+    pressed_at: Option<Duration>,
+    /// If the button was tapped in the last update.
+    tapped: bool,
+    /// When the last three taps happened, the most recent one last.
+    taps: [Option<Duration>; 3],
+}
+
+/// A tracker of the buttons over time.
+///
+/// The tracker must be updated with every new state, along with the time elapsed since the
+/// previous update (e.g. the frame time of a game). Using the elapsed time, instead of reading the
+/// clock, makes the tracker deterministic:
+///
+/// ```rust
+/// use duplosentido::mappings::{bits, Button};
+/// use duplosentido::tracker::ButtonTracker;
+/// use duplosentido::DualSenseState;
+/// use std::time::Duration;
+///
+/// let pressed = DualSenseState::from_button_bits(bits::CROSS);
+/// let released = DualSenseState::from_button_bits(0);
+///
+/// let mut tracker = ButtonTracker::new();
+/// let frame = Duration::from_millis(100);
+///
+/// tracker.update(&pressed, frame);
+/// tracker.update(&pressed, frame);
+/// assert_eq!(tracker.held_for(Button::Cross), frame);
+///
+/// tracker.update(&released, frame);
+/// assert!(tracker.was_tapped(Button::Cross));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ButtonTracker {
+    buttons: [TrackedButton; Button::ALL.len()],
+    /// The time elapsed since the tracker was created.
+    now: Duration,
+    tap_threshold: Duration,
+}
+
+impl ButtonTracker {
+    /// Create a tracker with all the buttons released.
+    pub fn new() -> Self {
+        ButtonTracker {
+            buttons: [TrackedButton::default(); Button::ALL.len()],
+            now: Duration::ZERO,
+            tap_threshold: DEFAULT_TAP_THRESHOLD,
+        }
+    }
+
+    /// Create a tracker with the given maximum duration of a tap.
+    ///
+    /// See [`was_tapped`] for more information.
+    ///
+    /// [`was_tapped`]: fn@Self::was_tapped
+    pub fn with_tap_threshold(tap_threshold: Duration) -> Self {
+        ButtonTracker {
+            tap_threshold,
+            ..ButtonTracker::new()
+        }
+    }
+
+    /// Get the maximum duration of a tap.
+    pub fn tap_threshold(&self) -> Duration {
+        self.tap_threshold
+    }
+
+    /// Update the tracker with a new `state`, read `elapsed` after the previous one.
+    pub fn update(&mut self, state: &DualSenseState, elapsed: Duration) {
+        self.now += elapsed;
+
+        for button in Button::ALL {
+            let now = self.now;
+            let tap_threshold = self.tap_threshold;
+            let tracked = &mut self.buttons[button as usize];
+
+            tracked.tapped = false;
+            match (tracked.pressed_at, state.button(button).is_pressed()) {
+                (None, true) => tracked.pressed_at = Some(now),
+                (Some(pressed_at), false) => {
+                    tracked.pressed_at = None;
+                    tracked.tapped = now - pressed_at <= tap_threshold;
+                    if tracked.tapped {
+                        tracked.taps = [tracked.taps[1], tracked.taps[2], Some(now)];
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Get for how long `button` has been held.
+    ///
+    /// If the button is released, the duration is zero.
+    pub fn held_for(&self, button: Button) -> Duration {
+        match self.buttons[button as usize].pressed_at {
+            Some(pressed_at) => self.now - pressed_at,
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Return `true` if `button` was tapped and `false` otherwise.
+    ///
+    /// A button is tapped when it is released after being held for at most the
+    /// [`tap_threshold`] (200ms by default). The tap is only reported in the update where the
+    /// button was released.
+    ///
+    /// [`tap_threshold`]: fn@Self::tap_threshold
+    pub fn was_tapped(&self, button: Button) -> bool {
+        self.buttons[button as usize].tapped
+    }
+
+    /// Return `true` if `button` was double-tapped and `false` otherwise.
+    ///
+    /// A button is double-tapped when it is [tapped] at most `window` after its previous tap. Just
+    /// like a tap, the double-tap is only reported in the update where the button was released.
+    ///
+    /// Only the second tap of a sequence of quick taps is a double-tap. As such, a third tap
+    /// inside the `window` does not count as another double-tap:
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::{bits, Button};
+    /// use duplosentido::tracker::ButtonTracker;
+    /// use duplosentido::DualSenseState;
+    /// use std::time::Duration;
+    ///
+    /// let pressed = DualSenseState::from_button_bits(bits::CROSS);
+    /// let released = DualSenseState::from_button_bits(0);
+    ///
+    /// let mut tracker = ButtonTracker::new();
+    /// let frame = Duration::from_millis(50);
+    /// let window = Duration::from_millis(300);
+    ///
+    /// let mut tap = |tracker: &mut ButtonTracker| {
+    ///     tracker.update(&pressed, frame);
+    ///     tracker.update(&released, frame);
+    ///     tracker.double_tapped(Button::Cross, window)
+    /// };
+    ///
+    /// assert!(!tap(&mut tracker));
+    /// assert!(tap(&mut tracker));
+    /// assert!(!tap(&mut tracker));
+    /// ```
+    ///
+    /// [tapped]: fn@Self::was_tapped
+    pub fn double_tapped(&self, button: Button, window: Duration) -> bool {
+        let tracked = &self.buttons[button as usize];
+        let chained = |from: Option<Duration>, to: Option<Duration>| match (from, to) {
+            (Some(from), Some(to)) => to - from <= window,
+            _ => false,
+        };
+
+        // The previous tap must not be chained to another one, otherwise this is a third tap.
+        tracked.tapped
+            && chained(tracked.taps[1], tracked.taps[2])
+            && !chained(tracked.taps[0], tracked.taps[1])
+    }
+
+    /// Return `true` if every one of the `buttons` is currently pressed and `false` otherwise.
+    ///
+    /// An empty chord is never pressed.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::{bits, Button};
+    /// use duplosentido::tracker::ButtonTracker;
+    /// use duplosentido::DualSenseState;
+    /// use std::time::Duration;
+    ///
+    /// let state = DualSenseState::from_button_bits(bits::CROSS | bits::L1);
+    ///
+    /// let mut tracker = ButtonTracker::new();
+    /// tracker.update(&state, Duration::from_millis(4));
+    ///
+    /// assert!(tracker.chord_pressed(&[Button::L1, Button::Cross]));
+    /// assert!(!tracker.chord_pressed(&[Button::L1, Button::Cross, Button::R1]));
+    /// assert!(!tracker.chord_pressed(&[]));
+    /// ```
+    pub fn chord_pressed(&self, buttons: &[Button]) -> bool {
+        !buttons.is_empty()
+            && buttons
+                .iter()
+                .all(|&button| self.buttons[button as usize].pressed_at.is_some())
+    }
+
+    /// Get the new muted state of the microphone, if it changed between the `previous` and the
+    /// `current` states.
+    ///
+    /// The muted state is toggled by the controller itself, so it is *not* the same as the `Mute`
+    /// button: the button may be pressed without the state changing (e.g. when no microphone is
+    /// available), and the state may change without the button being pressed (e.g. when muted by
+    /// the host). To react to the button, use [`was_tapped`] or [`just_pressed`] instead.
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::MutedState;
+    /// use duplosentido::tracker::ButtonTracker;
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// let unmuted = DualSenseState::from_raw(report).unwrap();
+    /// report[54] = 0b0000_0100; // Microphone muted.
+    /// let muted = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// assert_eq!(ButtonTracker::mic_mute_toggled(&unmuted, &muted), Some(MutedState::Muted));
+    /// assert_eq!(ButtonTracker::mic_mute_toggled(&muted, &unmuted), Some(MutedState::Unmuted));
+    /// assert_eq!(ButtonTracker::mic_mute_toggled(&muted, &muted), None);
+    /// ```
+    ///
+    /// [`was_tapped`]: fn@Self::was_tapped
+    /// [`just_pressed`]: fn@crate::DualSenseState::just_pressed
+    pub fn mic_mute_toggled(
+        previous: &DualSenseState,
+        current: &DualSenseState,
+    ) -> Option<MutedState> {
+        let muted = current.microphone().muted();
+
+        (muted != previous.microphone().muted()).then_some(muted)
+    }
+
+    /// Get the new plugged state of the headphone, if it changed between the `previous` and the
+    /// `current` states.
+    ///
+    /// This is useful to react to a headset being plugged or unplugged, such as switching the
+    /// audio output or showing a prompt:
+    ///
+    /// ```rust
+    /// use duplosentido::mappings::PluggedState;
+    /// use duplosentido::tracker::ButtonTracker;
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// let unplugged = DualSenseState::from_raw(report).unwrap();
+    /// report[54] = 0b0000_0001; // Headphone plugged.
+    /// let plugged = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// let changed = ButtonTracker::headphone_changed(&unplugged, &plugged);
+    /// assert_eq!(changed, Some(PluggedState::Plugged));
+    /// assert_eq!(ButtonTracker::headphone_changed(&plugged, &plugged), None);
+    /// ```
+    pub fn headphone_changed(
+        previous: &DualSenseState,
+        current: &DualSenseState,
+    ) -> Option<PluggedState> {
+        let headphone = current.headphone();
+
+        (headphone != previous.headphone()).then_some(headphone)
+    }
+
+    /// Get the new state of the microphone, if it was plugged, unplugged or switched between the
+    /// `previous` and the `current` states.
+    ///
+    /// A switch happens when the microphone in use changes from the internal one to an external
+    /// one (e.g. the microphone of a headset), or the other way around, as reported by
+    /// [`MicrophoneState::external`]. Changes to the muted state are not considered, see
+    /// [`mic_mute_toggled`] for them.
+    ///
+    /// ```rust
+    /// use duplosentido::tracker::ButtonTracker;
+    /// use duplosentido::DualSenseState;
+    ///
+    /// let mut report = [0_u8; 64];
+    /// report[0] = 0x01;
+    /// report[8] = 0x08;
+    /// let unplugged = DualSenseState::from_raw(report).unwrap();
+    /// report[54] = 0b0000_0010; // Microphone plugged.
+    /// report[55] = 0b0000_0001; // External microphone.
+    /// let plugged = DualSenseState::from_raw(report).unwrap();
+    ///
+    /// let changed = ButtonTracker::microphone_changed(&unplugged, &plugged).unwrap();
+    /// assert!(changed.is_plugged());
+    /// assert!(changed.external());
+    /// assert_eq!(ButtonTracker::microphone_changed(&plugged, &plugged), None);
+    /// ```
+    ///
+    /// [`MicrophoneState::external`]: fn@crate::mappings::MicrophoneState::external
+    /// [`mic_mute_toggled`]: fn@Self::mic_mute_toggled
+    pub fn microphone_changed(
+        previous: &DualSenseState,
+        current: &DualSenseState,
+    ) -> Option<MicrophoneState> {
+        let (previous, microphone) = (previous.microphone(), current.microphone());
+        let changed = microphone.plugged() != previous.plugged()
+            || microphone.external() != previous.external();
+
+        changed.then_some(microphone)
+    }
+}
+
+impl Default for ButtonTracker {
+    fn default() -> Self {
+        ButtonTracker::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mappings::bits;
+
+    const FRAME: Duration = Duration::from_millis(100);
+
+    /// Update `tracker` with `frames` states, one `FRAME` apart, where only the buttons in
+    /// `pressed` are held.
+    fn hold(tracker: &mut ButtonTracker, pressed: u32, frames: usize) {
+        let state = DualSenseState::from_button_bits(pressed);
+
+        for _ in 0..frames {
+            tracker.update(&state, FRAME);
+        }
+    }
+
+    #[test]
+    fn long_hold_is_not_a_tap() {
+        let mut tracker = ButtonTracker::new();
+
+        hold(&mut tracker, bits::CROSS, 21);
+        assert_eq!(tracker.held_for(Button::Cross), Duration::from_secs(2));
+        assert_eq!(tracker.held_for(Button::Circle), Duration::ZERO);
+
+        hold(&mut tracker, 0, 1);
+        assert_eq!(tracker.held_for(Button::Cross), Duration::ZERO);
+        assert!(!tracker.was_tapped(Button::Cross));
+    }
+
+    #[test]
+    fn quick_tap_is_reported_once() {
+        let mut tracker = ButtonTracker::new();
+
+        hold(&mut tracker, bits::CROSS, 1);
+        hold(&mut tracker, 0, 1);
+        assert!(tracker.was_tapped(Button::Cross));
+        assert!(!tracker.was_tapped(Button::Circle));
+
+        hold(&mut tracker, 0, 1);
+        assert!(!tracker.was_tapped(Button::Cross));
+    }
+
+    #[test]
+    fn tap_threshold_is_inclusive() {
+        let mut tracker = ButtonTracker::with_tap_threshold(FRAME * 2);
+
+        hold(&mut tracker, bits::CROSS, 2);
+        hold(&mut tracker, 0, 1);
+        assert!(tracker.was_tapped(Button::Cross));
+
+        hold(&mut tracker, bits::CROSS, 3);
+        hold(&mut tracker, 0, 1);
+        assert!(!tracker.was_tapped(Button::Cross));
+    }
+
+    #[test]
+    fn double_tap_sequence_restarts_after_window() {
+        let mut tracker = ButtonTracker::new();
+        let window = FRAME * 3;
+        let tap = |tracker: &mut ButtonTracker| {
+            hold(tracker, bits::CROSS, 1);
+            hold(tracker, 0, 1);
+            tracker.double_tapped(Button::Cross, window)
+        };
+
+        assert!(!tap(&mut tracker));
+        assert!(tap(&mut tracker));
+        assert!(!tap(&mut tracker));
+
+        hold(&mut tracker, 0, 10);
+        assert!(!tap(&mut tracker));
+        assert!(tap(&mut tracker));
+    }
+}